@@ -1,16 +1,91 @@
 export type GameGrid = string[][];
 
+export type WordPaths = Record<string, [number, number][]>;
+
+export type BoardShape = {
+  rows: number;
+  cols: number;
+  mask:
+    | 'full'
+    | 'diamond'
+    | 'cross'
+    | 'hexagon'
+    | { holes: [number, number][] };
+};
+
+// 'torus' boards wrap around, so squares on opposite edges are adjacent, and 'hex' boards are
+// sent in axial coordinates
+export type Topology = 'bounded' | 'torus' | 'hex';
+
+export type Modifier = 'doubleLetter' | 'tripleWord' | 'blocked' | 'wildcard';
+
 export type GameData = {
   id: string;
+  // squares masked out of irregular boards are empty strings
   grid: string[][];
+  shape: BoardShape;
+  topology: Topology;
+  // the special square on each square of the grid, or empty if there are none
+  modifiers: (Modifier | null)[][];
+};
+
+export type SocketResponse =
+  | GuessReponse
+  | GameResults
+  | Setup
+  | Lobby
+  | PlayerJoined
+  | PlayerLeft
+  | PlayerFound
+  | WordClaimed
+  | Team
+  | TeammateFound;
+
+// 'race' rooms let only the first player to submit each word claim it, and 'teams' rooms split
+// players into teams who share their words and score
+export type RoomMode = 'standard' | 'race' | 'teams';
+
+// a team's result in a game played in teams
+export type TeamStanding = {
+  team: number;
+  players: string[];
+  words: number;
+  score: number;
 };
 
-export type SocketResponse = GuessReponse | GameResults | Setup;
+// how the words found compare to those of the player who set a challenge
+export type HeadToHead = {
+  opponent: string;
+  opponentScore: number;
+  shared: string[];
+  onlyYours: string[];
+  onlyTheirs: string[];
+};
+
+// a player's result in a game played together in a room
+export type Standing = {
+  name: string;
+  words: number;
+  score: number;
+};
 
 type GuessReponse = {
   type: 'guessResponse';
   word: string;
   valid: boolean;
+  score: number;
+  reason?:
+    | 'malformed'
+    | 'alreadyFound'
+    | 'empty'
+    | 'outOfBounds'
+    | 'notAdjacent'
+    | 'reusedSquare'
+    | 'notAWord'
+    | 'ambiguous'
+    | 'alreadyClaimed';
+  // the player who already claimed the word, in race mode
+  claimedBy?: string;
 };
 
 type GameResults = {
@@ -18,9 +93,23 @@ type GameResults = {
   results: {
     foundWords: string[];
     missedWords: string[];
+    missedPaths: WordPaths;
     score: number;
+    targetWord: string | null;
+    targetPath: [number, number][] | null;
+    foundTargetWord: boolean;
   };
   msg: string | null;
+  // a short definition of each long missed word that has one
+  definitions?: Record<string, string>;
+  // every player's result, best first, in games played together in a room
+  standings?: Standing[];
+  // every team's result, best first, in team mode
+  teams?: TeamStanding[];
+  // the token others can play against the saved score with as a challenge
+  challengeId?: string;
+  // how the words found compare to the challenger's, in challenge games
+  headToHead?: HeadToHead;
 };
 
 type Setup = {
@@ -28,3 +117,58 @@ type Setup = {
   time: number;
   game: GameData;
 };
+
+// the waiting room of a lobby, sent on joining it
+type Lobby = {
+  type: 'lobby';
+  code: string;
+  // the name this player was given, numbered if someone else already had it
+  name: string;
+  players: string[];
+  host: string | null;
+  mode: RoomMode;
+};
+
+type PlayerJoined = {
+  type: 'playerJoined';
+  name: string;
+  // the team they're on, in team mode
+  team?: number;
+};
+
+type PlayerLeft = {
+  type: 'playerLeft';
+  name: string;
+  // the player who can now start the game
+  host: string | null;
+};
+
+// another player in the room found a word; the word itself isn't sent
+type PlayerFound = {
+  type: 'playerFound';
+  name: string;
+  words: number;
+  score: number;
+};
+
+// another player claimed a word in race mode, so it can no longer be found
+type WordClaimed = {
+  type: 'wordClaimed';
+  name: string;
+  word: string;
+};
+
+// the team this player is on, sent when a game played in teams starts
+type Team = {
+  type: 'team';
+  team: number;
+  teammates: string[];
+};
+
+// a teammate found a word for the team
+type TeammateFound = {
+  type: 'teammateFound';
+  name: string;
+  word: string;
+  score: number;
+};
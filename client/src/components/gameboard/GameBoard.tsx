@@ -1,7 +1,20 @@
 import React, { useContext, useEffect, useRef, useState } from 'react';
 import { Letter } from './Letter';
-import { GameData, GameGrid, SocketResponse } from '../../@types';
+import {
+  GameData,
+  GameGrid,
+  HeadToHead,
+  Modifier,
+  RoomMode,
+  SocketResponse,
+  Standing,
+  TeamStanding,
+  Topology,
+  WordPaths,
+} from '../../@types';
 import { GameOptions } from './GameOptions';
+import { Lobby } from './Lobby';
+import { gridTemplateColumns, tilePosition } from './boardLayout';
 import { useLocalStorageState } from '../../hooks/useLocalStorageState';
 import { GameResults } from './GameResults';
 import UserContext from '../../UserContext';
@@ -17,6 +30,8 @@ export const GameBoard: React.FC<GameBoardProps> = ({}) => {
   const errorTimeout = useRef<number | undefined>(undefined);
 
   const [grid, setGrid] = useState<GameGrid | null>(null);
+  const [topology, setTopology] = useState<Topology>('bounded');
+  const [modifiers, setModifiers] = useState<(Modifier | null)[][]>([]);
   const [foundWords, setFoundWords] = useState<string[]>([]);
   const [score, setScore] = useState(0);
   const [remainingTime, setRemainingTime] = useState<number>(lastTime);
@@ -31,7 +46,42 @@ export const GameBoard: React.FC<GameBoardProps> = ({}) => {
   const [preGame, setPreGame] = useState(true);
   const [postGame, setPostGame] = useState(false);
   const [missedWords, setMissedWords] = useState<string[]>([]);
+  const [missedPaths, setMissedPaths] = useState<WordPaths>({});
+  const [definitions, setDefinitions] = useState<Record<string, string>>({});
+  const [targetWord, setTargetWord] = useState<string | null>(null);
+  const [foundTargetWord, setFoundTargetWord] = useState(false);
   const [endgameMessage, setEndgameMessage] = useState<string | null>('');
+  // the other players in the room, with how many words they've found and their score so far
+  const [players, setPlayers] = useState<
+    Record<string, { words: number; score: number }>
+  >({});
+  const [standings, setStandings] = useState<Standing[] | null>(null);
+  // words other players claimed in race mode, with who claimed them
+  const [claimedWords, setClaimedWords] = useState<Record<string, string>>(
+    {}
+  );
+  // the team this player is on in team mode, and the teammate who found each
+  // of the team's words
+  const [team, setTeam] = useState<{
+    team: number;
+    teammates: string[];
+  } | null>(null);
+  const [teammateWords, setTeammateWords] = useState<Record<string, string>>(
+    {}
+  );
+  const [teamStandings, setTeamStandings] = useState<TeamStanding[] | null>(
+    null
+  );
+  const [challengeId, setChallengeId] = useState<string | null>(null);
+  const [headToHead, setHeadToHead] = useState<HeadToHead | null>(null);
+  // the waiting room, while in a lobby whose game hasn't started yet
+  const [lobby, setLobby] = useState<{
+    code: string;
+    name: string;
+    players: string[];
+    host: string | null;
+    mode: RoomMode;
+  } | null>(null);
 
   const socket = useRef<WebSocket | null>(null);
 
@@ -49,11 +99,33 @@ export const GameBoard: React.FC<GameBoardProps> = ({}) => {
     setLetterPath([...letterPath, [y, x]]);
   };
 
+  // the distance between two squares along one axis, counting wrapping around the edge on torus
+  // boards
+  const axisDistance = (a: number, b: number, length: number) => {
+    const distance = Math.abs(a - b);
+    return topology === 'torus'
+      ? Math.min(distance, length - distance)
+      : distance;
+  };
+
+  const isAdjacent = (
+    [ay, ax]: [number, number],
+    [by, bx]: [number, number]
+  ) => {
+    // hexes in axial coordinates aren't adjacent to the two squares that are diagonal down-right
+    // and up-left of them
+    if (topology === 'hex' && (by - ay) * (bx - ax) > 0) return false;
+    return (
+      axisDistance(ay, by, grid!.length) <= 1 &&
+      axisDistance(ax, bx, grid![0].length) <= 1
+    );
+  };
+
   const handleMouseOver = (y: number, x: number) => {
     if (!selectedWord) return;
     const [lastY, lastX] = letterPath[letterPath.length - 1];
     // return if they're trying to add a letter not adjacent to their last letter
-    if (Math.abs(lastY - y) > 1 || Math.abs(lastX - x) > 1) return;
+    if (!isAdjacent([lastY, lastX], [y, x])) return;
     if (
       letterPath[letterPath.length - 2] &&
       letterPath[letterPath.length - 2][0] === y &&
@@ -81,10 +153,16 @@ export const GameBoard: React.FC<GameBoardProps> = ({}) => {
     }
   };
 
-  const handleMouseUp = (n: number) => {
-    socket.current!.send(selectedWord);
+  const handleMouseUp = (grid: GameGrid) => {
+    // blank tiles can stand for any letter, so ask which word was meant
+    const word = letterPath.some(([y, x]) => grid[y][x] === '*')
+      ? window.prompt(`Which word does ${selectedWord} spell?`)
+      : null;
+    socket.current!.send(
+      JSON.stringify(word ? { path: letterPath, word } : letterPath)
+    );
     setSelectedWord('');
-    setSelectedLetters(Array(n).fill(Array(n).fill(false)));
+    setSelectedLetters(grid.map((row) => row.map(() => false)));
     setLetterPath([]);
   };
 
@@ -92,19 +170,65 @@ export const GameBoard: React.FC<GameBoardProps> = ({}) => {
     daily: boolean,
     id: string,
     size: number,
-    time?: number
+    time?: number,
+    together?: boolean,
+    mode?: RoomMode
   ) => {
     let url: string;
-    const queryString = time
-      ? new URLSearchParams({ time: time.toString() }).toString()
-      : '';
+    // guesses are sent as the path of letters traced, rather than the word itself
+    const params = new URLSearchParams({ paths: 'true', definitions: 'true' });
+    if (time) params.set('time', time.toString());
+    if (together && mode) params.set('mode', mode);
+    const queryString = params.toString();
 
-    if (daily) url = '/game/daily';
+    if (daily) url = `/game/daily?${queryString}`;
+    else if (id !== '' && together) url = `/game/room/${id}?${queryString}`;
     else if (id !== '') url = `/game/id/${id}?${queryString}`;
     else url = `/game/${size}?${queryString}`;
     handleSocket(window.location.host + url);
   };
 
+  // plays the board from someone else's score, against the same clock
+  const startChallenge = (challenge: string) => {
+    const params = new URLSearchParams({ paths: 'true', definitions: 'true' });
+    handleSocket(
+      window.location.host + `/game/challenge/${challenge}?${params.toString()}`
+    );
+  };
+
+  // challenge links open straight into the challenge, which is then dropped
+  // from the address so that starting a new game doesn't replay it
+  useEffect(() => {
+    const challenge = new URLSearchParams(window.location.search).get(
+      'challenge'
+    );
+    if (!challenge) return;
+    window.history.replaceState(null, '', window.location.pathname);
+    startChallenge(challenge);
+  }, []);
+
+  const startLobby = (
+    code: string | null,
+    size: number,
+    time: number,
+    name: string,
+    mode: RoomMode
+  ) => {
+    const params = new URLSearchParams({ paths: 'true', definitions: 'true' });
+    if (name.trim() !== '') params.set('name', name.trim());
+    if (code === null) {
+      params.set('time', time.toString());
+      params.set('mode', mode);
+    }
+    const queryString = params.toString();
+    handleSocket(
+      window.location.host +
+        (code === null
+          ? `/lobby/new/${size}?${queryString}`
+          : `/lobby/${code.toUpperCase()}?${queryString}`)
+    );
+  };
+
   const startTimer = () => {
     timerIntervalRef.current = setInterval(() => {
       setRemainingTime((t) => t! - 1);
@@ -119,6 +243,15 @@ export const GameBoard: React.FC<GameBoardProps> = ({}) => {
     setSelectedWord('');
     setLetterPath([]);
     setRemainingTime(lastTime);
+    setPlayers({});
+    setStandings(null);
+    setClaimedWords({});
+    setTeam(null);
+    setTeammateWords({});
+    setTeamStandings(null);
+    setChallengeId(null);
+    setHeadToHead(null);
+    setLobby(null);
     setPreGame(true);
     setPostGame(false);
   };
@@ -126,33 +259,108 @@ export const GameBoard: React.FC<GameBoardProps> = ({}) => {
   const handleSocket = (url: string) => {
     if (socket.current !== null) throw new Error('already have a socket open');
     socket.current = new WebSocket('ws://' + url);
+    // lobbies are closed if their game is never started
+    socket.current.onclose = () => {
+      socket.current = null;
+      setLobby(null);
+    };
     socket.current.onmessage = (event) => {
       const msg: SocketResponse = JSON.parse(event.data);
       switch (msg.type) {
         case 'setup':
           setGameId(msg.game.id);
           setGrid(msg.game.grid);
+          setTopology(msg.game.topology);
+          setModifiers(msg.game.modifiers);
           setSelectedLetters(
-            Array(msg.game.grid.length).fill(
-              Array(msg.game.grid.length).fill(false)
-            )
+            msg.game.grid.map((row) => row.map(() => false))
           );
           setRemainingTime(msg.time);
           setLastTime(msg.time);
           startTimer();
+          setLobby(null);
           setPreGame(false);
           break;
+        case 'lobby':
+          setLobby({
+            code: msg.code,
+            name: msg.name,
+            players: msg.players,
+            host: msg.host,
+            mode: msg.mode,
+          });
+          setPlayers(
+            Object.fromEntries(
+              msg.players
+                .filter((p) => p !== msg.name)
+                .map((p) => [p, { words: 0, score: 0 }])
+            )
+          );
+          break;
         case 'guessResponse':
           if (msg.valid) {
             setFoundWords((w) => [...w, msg.word]);
-            setScore((s) => s + 2 ** msg.word.length);
+            setScore((s) => s + msg.score);
           }
           break;
+        case 'playerJoined':
+          setPlayers((p) => ({ ...p, [msg.name]: { words: 0, score: 0 } }));
+          setLobby((l) => l && { ...l, players: [...l.players, msg.name] });
+          setTeam(
+            (t) =>
+              t &&
+              (msg.team === t.team
+                ? { ...t, teammates: [...t.teammates, msg.name] }
+                : t)
+          );
+          break;
+        case 'playerLeft':
+          setPlayers(({ [msg.name]: _, ...p }) => p);
+          setLobby(
+            (l) =>
+              l && {
+                ...l,
+                players: l.players.filter((p) => p !== msg.name),
+                host: msg.host,
+              }
+          );
+          break;
+        case 'team':
+          setTeam({ team: msg.team, teammates: msg.teammates });
+          break;
+        case 'teammateFound':
+          // teammates share their words and score
+          setFoundWords((w) => [...w, msg.word]);
+          setScore((s) => s + msg.score);
+          setTeammateWords((t) => ({ ...t, [msg.word]: msg.name }));
+          break;
+        case 'wordClaimed':
+          setClaimedWords((c) => ({ ...c, [msg.word]: msg.name }));
+          break;
+        case 'playerFound':
+          setPlayers((p) => ({
+            ...p,
+            [msg.name]: { words: msg.words, score: msg.score },
+          }));
+          break;
         case 'gameOver':
           clearInterval(timerIntervalRef.current);
           setFoundWords(msg.results.foundWords);
           setMissedWords(msg.results.missedWords);
+          setMissedPaths({
+            ...msg.results.missedPaths,
+            ...(msg.results.targetWord && msg.results.targetPath
+              ? { [msg.results.targetWord]: msg.results.targetPath }
+              : {}),
+          });
+          setDefinitions(msg.definitions ?? {});
+          setTargetWord(msg.results.targetWord);
+          setFoundTargetWord(msg.results.foundTargetWord);
           setEndgameMessage(msg.msg);
+          setStandings(msg.standings ?? null);
+          setTeamStandings(msg.teams ?? null);
+          setChallengeId(msg.challengeId ?? null);
+          setHeadToHead(msg.headToHead ?? null);
           socket.current = null;
           setPostGame(true);
           break;
@@ -162,6 +370,19 @@ export const GameBoard: React.FC<GameBoardProps> = ({}) => {
     };
   };
 
+  if (preGame && lobby) {
+    return (
+      <Lobby
+        code={lobby.code}
+        name={lobby.name}
+        players={lobby.players}
+        host={lobby.host}
+        mode={lobby.mode}
+        start={() => socket.current!.send('start')}
+      />
+    );
+  }
+
   if (preGame) {
     return (
       <GameOptions
@@ -169,6 +390,7 @@ export const GameBoard: React.FC<GameBoardProps> = ({}) => {
         size={size}
         setSize={setSize}
         startGame={startGame}
+        startLobby={startLobby}
         error={error}
       />
     );
@@ -181,7 +403,17 @@ export const GameBoard: React.FC<GameBoardProps> = ({}) => {
         foundWords={foundWords}
         score={score}
         missedWords={missedWords}
+        missedPaths={missedPaths}
+        definitions={definitions}
+        targetWord={targetWord}
+        foundTargetWord={foundTargetWord}
+        grid={grid!}
+        topology={topology}
         endgameMessage={endgameMessage}
+        standings={standings}
+        teamStandings={teamStandings}
+        challengeId={challengeId}
+        headToHead={headToHead}
         reset={reset}
       />
     );
@@ -193,25 +425,38 @@ export const GameBoard: React.FC<GameBoardProps> = ({}) => {
         <div
           className="grid"
           style={{
-            gridTemplateColumns: '6rem '.repeat(grid!.length),
+            gridTemplateColumns: gridTemplateColumns(grid!, topology, 6),
           }}
-          onMouseUp={() => handleMouseUp(grid!.length)}
+          onMouseUp={() => handleMouseUp(grid!)}
         >
           {grid!.map((row, y) =>
-            row.map((column, x) => (
-              <Letter
-                key={`${x},${y}`}
-                letter={column}
-                selected={selectedLetters![y][x]}
-                isNewestLetter={
-                  letterPath[0] &&
-                  letterPath[letterPath.length - 1][0] === y &&
-                  letterPath[letterPath.length - 1][1] === x
-                }
-                handleMouseDown={() => handleMouseDown(y, x)}
-                handleMouseOver={() => handleMouseOver(y, x)}
-              />
-            ))
+            row.map((column, x) =>
+              // masked out squares are left blank, and blocked squares are drawn as walls
+              column === '' ? (
+                <div
+                  key={`${x},${y}`}
+                  className={`h-24 w-24 ${
+                    modifiers[y]?.[x] === 'blocked' && 'bg-gray-700 rounded-md'
+                  }`}
+                  style={tilePosition(y, x, topology)}
+                />
+              ) : (
+                <Letter
+                  key={`${x},${y}`}
+                  letter={column}
+                  position={tilePosition(y, x, topology)}
+                  modifier={modifiers[y]?.[x] ?? null}
+                  selected={selectedLetters![y][x]}
+                  isNewestLetter={
+                    letterPath[0] &&
+                    letterPath[letterPath.length - 1][0] === y &&
+                    letterPath[letterPath.length - 1][1] === x
+                  }
+                  handleMouseDown={() => handleMouseDown(y, x)}
+                  handleMouseOver={() => handleMouseOver(y, x)}
+                />
+              )
+            )
           )}
         </div>
         <div>selected word: {selectedWord}</div>
@@ -233,16 +478,51 @@ export const GameBoard: React.FC<GameBoardProps> = ({}) => {
           </strong>
         </div>
         <div>
-          Score: <strong>{score}</strong>
+          {team ? <>Team score</> : <>Score</>}: <strong>{score}</strong>
         </div>
+        {team && (
+          <div className="mb-2">
+            Team {team.team + 1}
+            {team.teammates.length > 0 && (
+              <>, with {team.teammates.join(', ')}</>
+            )}
+          </div>
+        )}
+        {Object.keys(players).length > 0 && (
+          <>
+            Other players:
+            <ul className="list-disc list-inside mb-2">
+              {Object.entries(players).map(([name, { words, score }]) => (
+                <li className="text-sm" key={name}>
+                  {name}: {words} words, {score} points
+                </li>
+              ))}
+            </ul>
+          </>
+        )}
         Found words:
         <ul className="list-disc list-inside">
           {foundWords.map((word) => (
             <li className="text-sm" key={word}>
               {word}
+              {teammateWords[word] && (
+                <span className="text-gray-600"> ({teammateWords[word]})</span>
+              )}
             </li>
           ))}
         </ul>
+        {Object.keys(claimedWords).length > 0 && (
+          <>
+            Claimed by others:
+            <ul className="list-disc list-inside">
+              {Object.entries(claimedWords).map(([word, name]) => (
+                <li className="text-sm text-gray-600" key={word}>
+                  {word} ({name})
+                </li>
+              ))}
+            </ul>
+          </>
+        )}
       </div>
     </div>
   );
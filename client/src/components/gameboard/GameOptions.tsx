@@ -1,13 +1,45 @@
-import React, { useState } from 'react';
+import React, { useContext, useState } from 'react';
+import UserContext from '../../UserContext';
+import { RoomMode } from '../../@types';
 
 interface GameOptionsProps {
   remainingTime: number;
   size: number;
   setSize: (size: number) => void;
-  startGame: (daily: boolean, id: string, size: number, time: number) => void;
+  startGame: (
+    daily: boolean,
+    id: string,
+    size: number,
+    time: number,
+    together: boolean,
+    mode: RoomMode
+  ) => void;
+  // opens a new lobby when `code` is null, or joins the lobby with that code
+  startLobby: (
+    code: string | null,
+    size: number,
+    time: number,
+    name: string,
+    mode: RoomMode
+  ) => void;
   error: string | null;
 }
 
+const ModeSelect: React.FC<{
+  mode: RoomMode;
+  setMode: (mode: RoomMode) => void;
+}> = ({ mode, setMode }) => (
+  <select
+    name="mode"
+    value={mode}
+    onChange={(e) => setMode(e.target.value as RoomMode)}
+  >
+    <option value="standard">Everyone finds every word</option>
+    <option value="race">Race for words</option>
+    <option value="teams">Teams</option>
+  </select>
+);
+
 // const defaultTimes = {
 //   3: 60,
 //   4: 120,
@@ -21,11 +53,19 @@ export const GameOptions: React.FC<GameOptionsProps> = ({
   size,
   setSize,
   startGame,
+  startLobby,
   error,
 }) => {
+  const username = useContext(UserContext);
   const [advancedSettings, setAdvancedSettings] = useState(false);
   const [time, setTime] = useState(remainingTime);
   const [gameId, setGameId] = useState<string>('');
+  // join everyone else playing the game with this id, rather than playing it alone
+  const [together, setTogether] = useState(false);
+  const [lobbyCode, setLobbyCode] = useState('');
+  const [playerName, setPlayerName] = useState('');
+  // how players play against each other, in games played together
+  const [mode, setMode] = useState<RoomMode>('standard');
   return (
     <>
       <h2 className="m-2 text-xl font-semibold text-center">Game options:</h2>
@@ -70,6 +110,23 @@ export const GameOptions: React.FC<GameOptionsProps> = ({
               />
             </div>
           )}
+          {advancedSettings && gameId !== '' && (
+            <label className="my-2 flex justify-evenly">
+              <span className="inline-block">Play together:</span>
+              <input
+                type="checkbox"
+                name="together"
+                checked={together}
+                onChange={(e) => setTogether(e.target.checked)}
+              />
+            </label>
+          )}
+          {advancedSettings && gameId !== '' && together && (
+            <label className="my-2 flex justify-evenly">
+              <span className="inline-block">Mode:</span>
+              <ModeSelect mode={mode} setMode={setMode} />
+            </label>
+          )}
           {error && (
             <p className="text-red-600 font-semibold text-center">
               Error: {error}
@@ -85,7 +142,7 @@ export const GameOptions: React.FC<GameOptionsProps> = ({
                 onClick={(e) => {
                   e.preventDefault();
                   if (size < 3 || size > 7) return;
-                  startGame(false, gameId, size, (size - 1) * 30);
+                  startGame(false, gameId, size, (size - 1) * 30, false, mode);
                 }}
               />
             )}
@@ -96,7 +153,7 @@ export const GameOptions: React.FC<GameOptionsProps> = ({
                 value="Play daily game"
                 onClick={(e) => {
                   e.preventDefault();
-                  startGame(true, '', 4, remainingTime);
+                  startGame(true, '', 4, remainingTime, false, mode);
                 }}
                 disabled={remainingTime < 10 || remainingTime > 600}
               />
@@ -106,11 +163,17 @@ export const GameOptions: React.FC<GameOptionsProps> = ({
               <input
                 className="w-40 h-12 m-2 bg-indigo-700 text-white rounded-lg cursor-pointer hover:bg-indigo-500 active:bg-indigo-400 disabled:bg-indigo-300 disabled:cursor-default"
                 type={'submit'}
-                value={gameId === '' ? 'Create new game' : 'Load game from ID'}
+                value={
+                  gameId === ''
+                    ? 'Create new game'
+                    : together
+                    ? 'Join game'
+                    : 'Load game from ID'
+                }
                 onClick={(e) => {
                   e.preventDefault();
                   if (size < 3 || size > 7) return;
-                  startGame(false, gameId, size, time);
+                  startGame(false, gameId, size, time, together, mode);
                 }}
               />
             )}
@@ -121,6 +184,7 @@ export const GameOptions: React.FC<GameOptionsProps> = ({
               onClick={(e) => {
                 e.preventDefault();
                 setGameId('');
+                setTogether(false);
                 setAdvancedSettings(!advancedSettings);
               }}
             >
@@ -129,6 +193,66 @@ export const GameOptions: React.FC<GameOptionsProps> = ({
           </div>
         </form>
       </div>
+      <h2 className="m-2 text-xl font-semibold text-center">
+        Play with friends:
+      </h2>
+      <div className="flex justify-center">
+        <form className="flex flex-col mx-2 px-2 w-96 bg-gray-100 border border-gray-400 rounded-md">
+          {/* logged in players are shown by their username */}
+          {!username && (
+            <div className="flex justify-center mt-2">
+              <input
+                className="w-5/6"
+                placeholder="Your name"
+                maxLength={20}
+                value={playerName}
+                onChange={(e) => setPlayerName(e.target.value)}
+              />
+            </div>
+          )}
+          <label className="my-2 flex justify-evenly">
+            <span className="inline-block">Mode:</span>
+            <ModeSelect mode={mode} setMode={setMode} />
+          </label>
+          <div className="flex justify-center mt-2">
+            <input
+              className="w-5/6 uppercase"
+              placeholder="Lobby code"
+              maxLength={6}
+              value={lobbyCode}
+              onChange={(e) => setLobbyCode(e.target.value)}
+            />
+          </div>
+          <div className="flex justify-center">
+            <input
+              className="w-40 h-12 m-2 bg-indigo-700 text-white rounded-lg cursor-pointer hover:bg-indigo-500 active:bg-indigo-400 disabled:bg-indigo-300 disabled:cursor-default"
+              type={'submit'}
+              value="Create lobby"
+              onClick={(e) => {
+                e.preventDefault();
+                if (size < 3 || size > 7) return;
+                startLobby(
+                  null,
+                  size,
+                  advancedSettings ? time : (size - 1) * 30,
+                  playerName,
+                  mode
+                );
+              }}
+            />
+            <input
+              className="w-40 h-12 m-2 bg-indigo-700 text-white rounded-lg cursor-pointer hover:bg-indigo-500 active:bg-indigo-400 disabled:bg-indigo-300 disabled:cursor-default"
+              type={'submit'}
+              value="Join lobby"
+              onClick={(e) => {
+                e.preventDefault();
+                startLobby(lobbyCode.trim(), size, time, playerName, mode);
+              }}
+              disabled={lobbyCode.trim() === ''}
+            />
+          </div>
+        </form>
+      </div>
     </>
   );
 };
@@ -1,11 +1,30 @@
 import React, { useEffect, useState } from 'react';
+import {
+  GameGrid,
+  HeadToHead,
+  Standing,
+  TeamStanding,
+  Topology,
+  WordPaths,
+} from '../../@types';
+import { gridTemplateColumns, tilePosition } from './boardLayout';
 
 interface GameResultsProps {
   gameId: string;
   score: number;
   foundWords: string[];
   missedWords: string[];
+  missedPaths: WordPaths;
+  definitions: Record<string, string>;
+  targetWord: string | null;
+  foundTargetWord: boolean;
+  grid: GameGrid;
+  topology: Topology;
   endgameMessage: string | null;
+  standings: Standing[] | null;
+  teamStandings: TeamStanding[] | null;
+  challengeId: string | null;
+  headToHead: HeadToHead | null;
   reset: () => void;
 }
 
@@ -14,10 +33,37 @@ export const GameResults: React.FC<GameResultsProps> = ({
   score,
   foundWords,
   missedWords,
+  missedPaths,
+  definitions,
+  targetWord,
+  foundTargetWord,
+  grid,
+  topology,
   endgameMessage,
+  standings,
+  teamStandings,
+  challengeId,
+  headToHead,
   reset,
 }) => {
   const [copiedId, setCopiedId] = useState(false);
+  const [copiedChallenge, setCopiedChallenge] = useState(false);
+  // the missed word currently being traced on the board, and how many of its letters are shown
+  const [tracedWord, setTracedWord] = useState<string | null>(null);
+  const [tracedLength, setTracedLength] = useState(0);
+
+  useEffect(() => {
+    setTracedLength(0);
+    if (!tracedWord || !missedPaths[tracedWord]) return;
+    const interval = setInterval(() => {
+      setTracedLength((n) => Math.min(n + 1, missedPaths[tracedWord].length));
+    }, 250);
+    return () => clearInterval(interval);
+  }, [tracedWord]);
+
+  const tracedPath = tracedWord
+    ? (missedPaths[tracedWord] ?? []).slice(0, tracedLength)
+    : [];
 
   return (
     <div className="m-2">
@@ -39,7 +85,144 @@ export const GameResults: React.FC<GameResultsProps> = ({
       >
         {copiedId ? <>ID copied!</> : <>Copy Game ID</>}
       </button>
+      {challengeId !== null && (
+        <button
+          className={`btn-secondary w-44 h-8 ml-2
+          ${copiedChallenge ? 'cursor-default' : 'cursor-pointer'}
+          `}
+          disabled={copiedChallenge}
+          onClick={() => {
+            navigator.clipboard.writeText(
+              `${window.location.origin}/?challenge=${challengeId}`
+            );
+            setCopiedChallenge(true);
+          }}
+        >
+          {copiedChallenge ? <>Link copied!</> : <>Copy challenge link</>}
+        </button>
+      )}
       <h3>{endgameMessage && endgameMessage}</h3>
+      {headToHead && (
+        <div className="mt-2">
+          <p>
+            You scored <strong>{score}</strong> to {headToHead.opponent}'s{' '}
+            <strong>{headToHead.opponentScore}</strong>
+          </p>
+          <div className="flex mt-2">
+            {[
+              { heading: 'Only you found', words: headToHead.onlyYours },
+              { heading: 'You both found', words: headToHead.shared },
+              {
+                heading: `Only ${headToHead.opponent} found`,
+                words: headToHead.onlyTheirs,
+              },
+            ].map(({ heading, words }) => (
+              <div className="mr-8" key={heading}>
+                <p className="font-semibold">
+                  {heading} ({words.length})
+                </p>
+                <ul className="list-disc mx-6">
+                  {words.map((word) => (
+                    <li key={word}>{word}</li>
+                  ))}
+                </ul>
+              </div>
+            ))}
+          </div>
+        </div>
+      )}
+      {teamStandings && (
+        <table className="mt-2">
+          <thead>
+            <tr>
+              <th className="pr-4 text-left">Team</th>
+              <th className="pr-4 text-right">Words</th>
+              <th className="text-right">Score</th>
+            </tr>
+          </thead>
+          <tbody>
+            {teamStandings.map(({ team, players, words, score }) => (
+              <tr key={team}>
+                <td className="pr-4">
+                  Team {team + 1} ({players.join(', ')})
+                </td>
+                <td className="pr-4 text-right">{words}</td>
+                <td className="text-right">{score}</td>
+              </tr>
+            ))}
+          </tbody>
+        </table>
+      )}
+      {standings && (
+        <table className="mt-2">
+          <thead>
+            <tr>
+              <th className="pr-4 text-left">Player</th>
+              <th className="pr-4 text-right">Words</th>
+              <th className="text-right">Score</th>
+            </tr>
+          </thead>
+          <tbody>
+            {standings.map(({ name, words, score }, i) => (
+              <tr key={i}>
+                <td className="pr-4">{name}</td>
+                <td className="pr-4 text-right">{words}</td>
+                <td className="text-right">{score}</td>
+              </tr>
+            ))}
+          </tbody>
+        </table>
+      )}
+      {targetWord && (
+        <p className="mt-2">
+          {foundTargetWord ? (
+            <>
+              You found the hidden word <strong>{targetWord}</strong>!
+            </>
+          ) : (
+            <>
+              The hidden word was{' '}
+              <strong
+                className="cursor-pointer"
+                onMouseEnter={() => setTracedWord(targetWord)}
+                onMouseLeave={() => setTracedWord(null)}
+              >
+                {targetWord}
+              </strong>
+            </>
+          )}
+        </p>
+      )}
+      <div
+        className="grid mt-4 w-fit"
+        style={{
+          gridTemplateColumns: gridTemplateColumns(grid, topology, 2.5),
+        }}
+      >
+        {grid.map((row, y) =>
+          row.map((letter, x) =>
+            letter === '' ? (
+              <div
+                key={`${x},${y}`}
+                className="h-10 w-10"
+                style={tilePosition(y, x, topology)}
+              />
+            ) : (
+              <div
+                key={`${x},${y}`}
+                style={tilePosition(y, x, topology)}
+                className={`rounded-full h-10 w-10 flex justify-center items-center select-none border border-gray-800 ${
+                  tracedPath.some(([py, px]) => py === y && px === x)
+                    ? 'bg-red-300'
+                    : 'bg-yellow-200'
+                }`}
+              >
+                {letter.charAt(0).toUpperCase() + letter.slice(1)}
+              </div>
+            )
+          )
+        )}
+      </div>
       <p className="mt-4">Words in this puzzle:</p>
       <ul className="list-disc mx-6">
         {foundWords.map((word) => (
@@ -48,8 +231,19 @@ export const GameResults: React.FC<GameResultsProps> = ({
           </li>
         ))}
         {missedWords.map((word) => (
-          <li className="text-red-800" key={word}>
+          <li
+            className="text-red-800 cursor-pointer"
+            key={word}
+            onMouseEnter={() => setTracedWord(word)}
+            onMouseLeave={() => setTracedWord(null)}
+          >
             {word}
+            {definitions[word] && (
+              <span className="text-gray-600 text-sm">
+                {' '}
+                &mdash; {definitions[word]}
+              </span>
+            )}
           </li>
         ))}
       </ul>
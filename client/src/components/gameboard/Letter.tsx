@@ -1,7 +1,16 @@
 import React from 'react';
+import { Modifier } from '../../@types';
+
+const modifierLabels: Partial<Record<Modifier, string>> = {
+  doubleLetter: 'DL',
+  tripleWord: 'TW',
+};
 
 interface LetterProps {
   letter: string;
+  // where the tile sits in the board's grid, if not simply the next cell
+  position?: React.CSSProperties;
+  modifier: Modifier | null;
   selected: boolean;
   isNewestLetter: boolean;
   handleMouseDown: React.MouseEventHandler;
@@ -12,6 +21,8 @@ interface LetterProps {
 
 export const Letter: React.FC<LetterProps> = ({
   letter,
+  position,
+  modifier,
   selected,
   isNewestLetter,
   handleMouseDown,
@@ -19,7 +30,7 @@ export const Letter: React.FC<LetterProps> = ({
 }) => {
   return (
     <div
-      className={`rounded-full h-24 w-24 flex justify-center items-center text-3xl cursor-pointer select-none border border-gray-800  ${
+      className={`relative rounded-full h-24 w-24 flex justify-center items-center text-3xl cursor-pointer select-none border border-gray-800  ${
         selected ? 'bg-indigo-300' : 'bg-yellow-200'
       }
       ${isNewestLetter && 'bg-indigo-400'}
@@ -27,12 +38,21 @@ export const Letter: React.FC<LetterProps> = ({
       onMouseDown={handleMouseDown}
       onMouseOver={handleMouseOver}
       style={{
+        ...position,
         // backgroundColor: selected ? 'yellow' : 'lightgreen',
         // hacky "grow border inside only" solution
         boxShadow: isNewestLetter ? '0px 0px 0px 3px black inset' : '',
       }}
     >
-      {letter.toUpperCase()}
+      {/* multi-letter tiles are shown like "Qu", and blank tiles as "?" */}
+      {letter === '*'
+        ? '?'
+        : letter.charAt(0).toUpperCase() + letter.slice(1)}
+      {modifier && modifierLabels[modifier] && (
+        <span className="absolute top-2 text-xs font-semibold text-red-700">
+          {modifierLabels[modifier]}
+        </span>
+      )}
     </div>
   );
 };
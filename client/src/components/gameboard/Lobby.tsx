@@ -0,0 +1,66 @@
+import React, { useState } from 'react';
+import { RoomMode } from '../../@types';
+
+interface LobbyProps {
+  code: string;
+  name: string;
+  players: string[];
+  host: string | null;
+  mode: RoomMode;
+  start: () => void;
+}
+
+export const Lobby: React.FC<LobbyProps> = ({
+  code,
+  name,
+  players,
+  host,
+  mode,
+  start,
+}) => {
+  const [copiedCode, setCopiedCode] = useState(false);
+  return (
+    <div className="flex flex-col items-center m-2 mt-8">
+      <h2 className="text-xl font-semibold">
+        Lobby code: <span className="font-mono">{code}</span>
+      </h2>
+      <button
+        className="btn-secondary w-28 h-8 my-2"
+        disabled={copiedCode}
+        onClick={() => {
+          navigator.clipboard.writeText(code);
+          setCopiedCode(true);
+        }}
+      >
+        {copiedCode ? <>Code copied!</> : <>Copy code</>}
+      </button>
+      {mode === 'race' && (
+        <p className="mb-2">
+          Race for words: only the first player to find a word gets it.
+        </p>
+      )}
+      {mode === 'teams' && (
+        <p className="mb-2">
+          Teams: players are split into teams who share their words and score.
+        </p>
+      )}
+      <p>Players:</p>
+      <ul className="list-disc list-inside mb-2">
+        {players.map((player) => (
+          <li key={player}>
+            {player}
+            {player === name && <> (you)</>}
+            {player === host && <> &mdash; host</>}
+          </li>
+        ))}
+      </ul>
+      {host === name ? (
+        <button className="btn-primary w-36 h-10" onClick={start}>
+          Start game
+        </button>
+      ) : (
+        <p>Waiting for {host ?? 'the host'} to start the game...</p>
+      )}
+    </div>
+  );
+};
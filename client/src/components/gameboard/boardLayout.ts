@@ -0,0 +1,23 @@
+import React from 'react';
+import { GameGrid, Topology } from '../../@types';
+
+// Hex boards are sent in axial coordinates, with each row drawn half a tile right of the row above
+// it. They're laid out on a grid of half-tile columns, with each tile spanning two of them.
+
+export const gridTemplateColumns = (
+  grid: GameGrid,
+  topology: Topology,
+  tileSize: number
+) =>
+  topology === 'hex'
+    ? `${tileSize / 2}rem `.repeat(2 * grid[0].length + grid.length - 1)
+    : `${tileSize}rem `.repeat(grid[0].length);
+
+export const tilePosition = (
+  y: number,
+  x: number,
+  topology: Topology
+): React.CSSProperties | undefined =>
+  topology === 'hex'
+    ? { gridRow: y + 1, gridColumn: `${2 * x + y + 1} / span 2` }
+    : undefined;
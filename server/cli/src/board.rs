@@ -0,0 +1,88 @@
+use wordtwist::words::Grid;
+
+/// How a hole in the board is written, since empty tiles can't be.
+const HOLE: &str = ".";
+
+/// Parses a board from `text`, which is either a JSON array of rows of tiles, or a row of tiles per
+/// line.
+///
+/// Tiles in a line are separated by whitespace, unless there is none, in which case every
+/// character is a tile. Holes in the board are written as ".", and blank squares as "*". Tiles are
+/// lowercased, and blank lines are ignored.
+pub fn parse_board(text: &str) -> Result<Grid, String> {
+    let grid: Grid = if text.trim_start().starts_with('[') {
+        serde_json::from_str(text).map_err(|e| format!("invalid JSON board: {e}"))?
+    } else {
+        text.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                if line.contains(char::is_whitespace) {
+                    line.split_whitespace().map(str::to_string).collect()
+                } else {
+                    line.chars().map(String::from).collect()
+                }
+            })
+            .collect()
+    };
+    if grid.is_empty() {
+        return Err("the board is empty".to_string());
+    }
+    if grid.iter().any(|row| row.len() != grid[0].len()) {
+        return Err("the rows of the board have different lengths".to_string());
+    }
+    Ok(grid
+        .into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|tile| match tile.as_str() {
+                    HOLE => String::new(),
+                    _ => tile.to_lowercase(),
+                })
+                .collect()
+        })
+        .collect())
+}
+
+/// Formats `grid` as a row of tiles per line, in the format read by [`parse_board`].
+pub fn format_board(grid: &Grid) -> String {
+    grid.iter()
+        .map(|row| {
+            row.iter()
+                .map(|tile| if tile.is_empty() { HOLE } else { tile })
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_text_boards() {
+        let grid = parse_board("CAT\nx.s\n\n").unwrap();
+        assert_eq!(grid, [["c", "a", "t"], ["x", "", "s"]]);
+        let grid = parse_board("qu a *\n t . s").unwrap();
+        assert_eq!(grid, [["qu", "a", "*"], ["t", "", "s"]]);
+
+        assert!(parse_board("\n\n").is_err());
+        assert!(parse_board("cat\nxs").is_err());
+    }
+
+    #[test]
+    fn parse_json_boards() {
+        let grid = parse_board(r#"[["qu", "a"], [".", "T"]]"#).unwrap();
+        assert_eq!(grid, [["qu", "a"], ["", "t"]]);
+        assert!(parse_board("[[\"a\"], ").is_err());
+    }
+
+    #[test]
+    fn boards_round_trip() {
+        let grid = parse_board("qu a *\nt . s").unwrap();
+        assert_eq!(format_board(&grid), "qu a *\nt . s");
+        assert_eq!(parse_board(&format_board(&grid)).unwrap(), grid);
+    }
+}
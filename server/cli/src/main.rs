@@ -0,0 +1,268 @@
+mod board;
+
+use std::{
+    error::Error,
+    io::{self, Read, Write},
+    path::PathBuf,
+};
+
+use board::{format_board, parse_board};
+use clap::{builder::RangedU64ValueParser, Parser, Subcommand, ValueEnum};
+use serde::{de::DeserializeOwned, Serialize};
+use wordtwist::{
+    game::{
+        BoardConstraints, BoardStats, Difficulty, Game, GameConfig, GameRules, Scoring,
+        ScoringRule, Shape,
+    },
+    words::{Dictionary, Grid, Path, Topology},
+};
+
+/// Generates, solves and measures word twist boards.
+#[derive(Parser)]
+#[command(name = "wordtwist-cli")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// How results are printed.
+    #[arg(long, global = true, value_enum, default_value_t = Format::Text)]
+    format: Format,
+    /// A word list, with one word per line, to use in place of the built-in dictionary.
+    #[arg(long, global = true)]
+    dictionary: Option<PathBuf>,
+    /// How the squares of the board connect: bounded, torus or hex.
+    #[arg(long, global = true, default_value = "bounded", value_parser = parse_value::<Topology>)]
+    topology: Topology,
+    /// The fewest letters a word can have.
+    #[arg(long, global = true)]
+    min_word_length: Option<usize>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Generates a board, and prints it.
+    Generate {
+        /// The number of rows, and of columns unless --cols is given. Boards have 3 to 7 of each,
+        /// as on the web.
+        #[arg(long, default_value_t = 4, value_parser = board_size())]
+        size: usize,
+        #[arg(long, value_parser = board_size())]
+        cols: Option<usize>,
+        /// The seed to generate the board from, chosen at random if not given.
+        #[arg(long)]
+        seed: Option<u64>,
+        /// How hard the board should be: easy, medium or hard.
+        #[arg(long, value_parser = parse_value::<Difficulty>)]
+        difficulty: Option<Difficulty>,
+    },
+    /// Reads a board from stdin, and lists the words in it with their paths and scores.
+    ///
+    /// Boards are given a row per line, with tiles separated by spaces, or as a JSON array of rows.
+    /// Holes are written as "." and blank squares as "*".
+    Solve {
+        /// How words are scored: exponential, classic, letterValue or targetBonus.
+        #[arg(long, default_value = "exponential", value_parser = parse_value::<Scoring>)]
+        scoring: Scoring,
+    },
+    /// Reads a board from stdin, and reports how many words it has and how hard it is.
+    Stats,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+}
+
+/// Parses a command line value into one of the crate's enums, which are named as they are in JSON.
+fn parse_value<T: DeserializeOwned>(value: &str) -> Result<T, String> {
+    serde_json::from_value(serde_json::Value::String(value.to_string()))
+        .map_err(|_| format!("unknown value \"{value}\""))
+}
+
+/// Parses a number of rows or columns, which can't be fewer than a word needs, or more than a board
+/// can be solved quickly with.
+fn board_size() -> RangedU64ValueParser<usize> {
+    RangedU64ValueParser::new().range(3..=7)
+}
+
+#[derive(Serialize)]
+struct GeneratedBoard<'a> {
+    seed: u64,
+    grid: &'a Grid,
+    topology: Topology,
+}
+
+#[derive(Serialize)]
+struct FoundWord<'a> {
+    word: &'a str,
+    score: usize,
+    path: &'a Path,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Stats<'a> {
+    #[serde(flatten)]
+    stats: &'a BoardStats,
+    band: Difficulty,
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    match run(Cli::parse()) {
+        // output piped into a command that stops reading early, such as `head`, isn't an error
+        Err(e)
+            if e.downcast_ref::<io::Error>()
+                .is_some_and(|e| e.kind() == io::ErrorKind::BrokenPipe) =>
+        {
+            Ok(())
+        }
+        result => result,
+    }
+}
+
+fn run(cli: Cli) -> Result<(), Box<dyn Error>> {
+    let mut out = io::stdout().lock();
+    let custom_dictionary = match &cli.dictionary {
+        Some(path) => {
+            let id = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or("custom");
+            Some(Dictionary::from_file(id, path)?)
+        }
+        None => None,
+    };
+    let dictionary = custom_dictionary
+        .as_ref()
+        .unwrap_or_else(|| Dictionary::embedded());
+    let config = GameConfig {
+        dictionary,
+        topology: cli.topology,
+        rules: GameRules {
+            min_word_length: cli
+                .min_word_length
+                .unwrap_or(GameRules::default().min_word_length),
+            ..Default::default()
+        },
+        ..GameConfig::new(4)
+    };
+
+    match cli.command {
+        Command::Generate {
+            size,
+            cols,
+            seed,
+            difficulty,
+        } => {
+            let config = GameConfig {
+                shape: Shape {
+                    cols: cols.unwrap_or(size),
+                    ..Shape::square(size)
+                },
+                constraints: BoardConstraints {
+                    difficulty,
+                    ..Default::default()
+                },
+                ..config
+            };
+            if !config.has_target_words() {
+                return Err("no words are short enough to hide in a board of this size".into());
+            }
+            let game = match seed {
+                Some(seed) => Game::generate_from_seed(&config, seed),
+                None => Game::from_config(&config),
+            };
+            let seed = game.seed().expect("generated games have a seed");
+            match cli.format {
+                Format::Text => {
+                    writeln!(out, "{}", format_board(game.grid()))?;
+                    writeln!(out, "seed: {seed}")?;
+                }
+                Format::Json => print_json(
+                    &mut out,
+                    &GeneratedBoard {
+                        seed,
+                        grid: game.grid(),
+                        topology: game.topology(),
+                    },
+                )?,
+            }
+        }
+        Command::Solve { scoring } => {
+            let game = Game::from_grid(&config, read_board()?).with_scoring(scoring);
+            let mut words: Vec<FoundWord> = game
+                .solution()
+                .words()
+                .map(|word| FoundWord {
+                    word,
+                    score: game.scoring().score_word(&game, word),
+                    path: game.solution().path(word).unwrap(),
+                })
+                .collect();
+            words.sort_by(|a, b| b.score.cmp(&a.score).then(a.word.cmp(b.word)));
+            match cli.format {
+                Format::Text => {
+                    for FoundWord { word, score, path } in &words {
+                        let path: Vec<String> =
+                            path.iter().map(|(y, x)| format!("{y},{x}")).collect();
+                        writeln!(out, "{word:<16} {score:>5}  {}", path.join(" "))?;
+                    }
+                }
+                Format::Json => print_json(&mut out, &words)?,
+            }
+        }
+        Command::Stats => {
+            let game = Game::from_grid(&config, read_board()?);
+            let stats = game.stats();
+            match cli.format {
+                Format::Text => {
+                    writeln!(out, "words: {}", stats.total_words)?;
+                    writeln!(out, "long words: {}", stats.long_words)?;
+                    writeln!(out, "most repeated tile: {}", stats.max_repeated_tile)?;
+                    writeln!(
+                        out,
+                        "difficulty: {} ({:?})",
+                        stats.difficulty,
+                        Difficulty::of_score(stats.difficulty)
+                    )?;
+                }
+                Format::Json => print_json(
+                    &mut out,
+                    &Stats {
+                        stats,
+                        band: Difficulty::of_score(stats.difficulty),
+                    },
+                )?,
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Reads a board from stdin.
+fn read_board() -> Result<Grid, Box<dyn Error>> {
+    let mut text = String::new();
+    io::stdin().read_to_string(&mut text)?;
+    Ok(parse_board(&text)?)
+}
+
+fn print_json<T: Serialize>(out: &mut impl Write, value: &T) -> io::Result<()> {
+    serde_json::to_writer_pretty(&mut *out, value)?;
+    writeln!(out)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn board_sizes_are_bounded() {
+        let parse =
+            |args: &[&str]| Cli::try_parse_from(["wordtwist-cli", "generate"].iter().chain(args));
+        assert!(parse(&["--size", "3", "--cols", "7"]).is_ok());
+        assert!(parse(&["--size", "2"]).is_err());
+        assert!(parse(&["--size", "8"]).is_err());
+        assert!(parse(&["--size", "100000"]).is_err());
+        assert!(parse(&["--cols", "0"]).is_err());
+    }
+}
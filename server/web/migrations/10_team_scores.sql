@@ -0,0 +1,5 @@
+ALTER TABLE scores 
+ADD COLUMN team INTEGER;
+
+ALTER TABLE scores 
+ADD COLUMN team_score INTEGER;
\ No newline at end of file
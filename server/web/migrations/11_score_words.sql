@@ -0,0 +1,2 @@
+ALTER TABLE scores 
+ADD COLUMN words TEXT;
\ No newline at end of file
@@ -0,0 +1,4 @@
+ALTER TABLE scores 
+ADD COLUMN challenge TEXT;
+
+CREATE UNIQUE INDEX idx_challenge ON scores (challenge);
\ No newline at end of file
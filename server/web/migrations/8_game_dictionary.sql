@@ -0,0 +1 @@
+ALTER TABLE games ADD COLUMN dictionary TEXT;
\ No newline at end of file
@@ -0,0 +1 @@
+ALTER TABLE games ADD COLUMN shape TEXT;
\ No newline at end of file
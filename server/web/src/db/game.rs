@@ -20,11 +20,13 @@ pub fn insert_game(conn: &mut Connection, game: &GameData) -> Result<Uuid> {
     let uuid = Uuid::new_v4();
 
     conn.execute(
-        "INSERT INTO games (id, game_data, size) VALUES (?1, ?2, ?3)",
+        "INSERT INTO games (id, game_data, size, dictionary, shape) VALUES (?1, ?2, ?3, ?4, ?5)",
         (
             uuid.to_string(),
             serde_json::to_string(game).unwrap(),
             game.size(),
+            game.dictionary(),
+            serde_json::to_string(&game.shape()).unwrap(),
         ),
     )?;
     Ok(uuid)
@@ -58,18 +60,93 @@ pub fn set_daily(conn: &mut Connection, id: Uuid) -> Result<()> {
     Ok(())
 }
 
+/// Adds `user_id`'s score for a game, along with the words they found, returning the token others
+/// can play against the new score with as a challenge.
 pub fn add_game_score(
     conn: &mut Connection,
     game_id: Uuid,
     user_id: UserID,
     score: usize,
     time: usize,
-) -> Result<()> {
-    conn.execute(
-        "INSERT INTO scores (game_id, user_id, score, time) VALUES (?1, ?2, ?3, ?4)",
-        (game_id.to_string(), user_id.0, score, time),
-    )?;
-    Ok(())
+    words: &[String],
+) -> Result<String> {
+    conn.query_row(
+        "INSERT INTO scores (game_id, user_id, score, time, words, challenge) VALUES (?1, ?2, ?3, ?4, ?5, ?6) RETURNING challenge",
+        (
+            game_id.to_string(),
+            user_id.0,
+            score,
+            time,
+            serde_json::to_string(words).unwrap(),
+            new_challenge(),
+        ),
+        |r| r.get(0),
+    )
+}
+
+/// Adds `user_id`'s score for a game played in team mode, along with their team and its score,
+/// returning the token others can play against the new score with as a challenge.
+#[allow(clippy::too_many_arguments)]
+pub fn add_team_game_score(
+    conn: &mut Connection,
+    game_id: Uuid,
+    user_id: UserID,
+    score: usize,
+    time: usize,
+    words: &[String],
+    team: usize,
+    team_score: usize,
+) -> Result<String> {
+    conn.query_row(
+        "INSERT INTO scores (game_id, user_id, score, time, words, team, team_score, challenge) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8) RETURNING challenge",
+        (
+            game_id.to_string(),
+            user_id.0,
+            score,
+            time,
+            serde_json::to_string(words).unwrap(),
+            team,
+            team_score,
+            new_challenge(),
+        ),
+        |r| r.get(0),
+    )
+}
+
+/// Returns a new challenge token, which is random so that challenges can't be found by guessing.
+fn new_challenge() -> String {
+    Uuid::new_v4().simple().to_string()
+}
+
+/// A saved score, as shown to someone challenged to beat it.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ScoreRecord {
+    pub game_id: Uuid,
+    pub username: String,
+    pub score: usize,
+    pub time: usize,
+    /// The words found, which are empty for scores saved before found words were recorded.
+    pub words: Vec<String>,
+}
+
+/// Returns the score that can be played against with the `challenge` token.
+pub fn get_score_by_challenge(conn: &mut Connection, challenge: &str) -> Result<ScoreRecord> {
+    conn.query_row(
+        "SELECT scores.game_id, users.username, scores.score, scores.time, scores.words FROM scores JOIN users ON scores.user_id = users.id WHERE scores.challenge=?1",
+        (challenge,),
+        |r| {
+            Ok(ScoreRecord {
+                game_id: Uuid::parse_str(&r.get::<usize, String>(0)?).unwrap(),
+                username: r.get(1)?,
+                score: r.get(2)?,
+                time: r.get(3)?,
+                words: r
+                    .get::<usize, Option<String>>(4)?
+                    .map(|words| serde_json::from_str(&words).unwrap())
+                    .unwrap_or_default(),
+            })
+        },
+    )
 }
 
 pub fn get_game_score(conn: &mut Connection, game_id: Uuid, user_id: UserID) -> Result<usize> {
@@ -103,7 +180,7 @@ pub fn get_game_stats(conn: &mut Connection, game_id: Uuid, max_time: usize) ->
 
 #[cfg(test)]
 mod test {
-    use crate::db::open_db_connection;
+    use crate::db::{open_db_connection, user::add_user};
 
     use super::*;
 
@@ -116,7 +193,11 @@ mod test {
                 user_id INTEGER,
                 score, INTEGER,
                 size INTEGER,
-                time INTEGER
+                time INTEGER,
+                team INTEGER,
+                team_score INTEGER,
+                words TEXT,
+                challenge TEXT
             );",
             (),
         )
@@ -141,19 +222,68 @@ mod test {
         let uid = UserID(500);
         let game_uuid = Uuid::new_v4();
 
-        add_game_score(&mut conn, game_uuid, uid, 9001, 5).unwrap();
+        add_game_score(&mut conn, game_uuid, uid, 9001, 5, &[]).unwrap();
         assert_eq!(get_game_score(&mut conn, game_uuid, uid).unwrap(), 9001)
     }
 
+    #[test]
+    fn scores_by_challenge() {
+        let mut conn = open_db_connection();
+        let username = Uuid::new_v4().to_string();
+        let uid = add_user(
+            &mut conn,
+            &username,
+            &format!("{username}@test.com"),
+            "hunter2",
+        )
+        .unwrap();
+        let game_uuid = insert_game(&mut conn, &GameData::new(4)).unwrap();
+        let words = vec!["cat".to_string(), "dog".to_string()];
+
+        let challenge = add_game_score(&mut conn, game_uuid, uid, 12, 90, &words).unwrap();
+        assert_eq!(
+            get_score_by_challenge(&mut conn, &challenge).unwrap(),
+            ScoreRecord {
+                game_id: game_uuid,
+                username,
+                score: 12,
+                time: 90,
+                words,
+            }
+        );
+        assert!(get_score_by_challenge(&mut conn, &new_challenge()).is_err());
+    }
+
+    #[test]
+    fn team_scores() {
+        let mut conn = setup_test_db();
+        let game_uuid = Uuid::new_v4();
+
+        add_team_game_score(&mut conn, game_uuid, UserID(7), 40, 60, &[], 1, 100).unwrap();
+        assert_eq!(get_game_score(&mut conn, game_uuid, UserID(7)).unwrap(), 40);
+        let team: (usize, usize) = conn
+            .query_row(
+                "SELECT team, team_score FROM scores WHERE game_id=?1 AND user_id=?2",
+                (game_uuid.to_string(), 7),
+                |r| Ok((r.get(0)?, r.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(team, (1, 100));
+    }
+
     #[test]
     fn score_stats() {
         let mut conn = setup_test_db();
         let game_uuid = Uuid::new_v4();
 
-        add_game_score(&mut conn, game_uuid, UserID(2), 60, 30).unwrap();
-        add_game_score(&mut conn, game_uuid, UserID(3), 30, 30).unwrap();
-        add_game_score(&mut conn, game_uuid, UserID(5), 90, 30).unwrap();
-        add_game_score(&mut conn, game_uuid, UserID(5), 10000, 60).unwrap();
+        let challenge = add_game_score(&mut conn, game_uuid, UserID(2), 60, 30, &[]).unwrap();
+        // challenges are random rather than counted up, so they can't be guessed from one another
+        assert_ne!(
+            add_game_score(&mut conn, game_uuid, UserID(3), 30, 30, &[]).unwrap(),
+            challenge
+        );
+        add_game_score(&mut conn, game_uuid, UserID(5), 90, 30, &[]).unwrap();
+        add_game_score(&mut conn, game_uuid, UserID(5), 10000, 60, &[]).unwrap();
 
         let stats = get_game_stats(&mut conn, game_uuid, 30).unwrap();
         assert_eq!(stats.count, 3);
@@ -49,6 +49,14 @@ pub fn validate_user(conn: &mut Connection, username: &str, password: &str) -> R
     Ok(UserID(id.unwrap()))
 }
 
+pub fn get_username(conn: &mut Connection, user: UserID) -> Result<String> {
+    Ok(
+        conn.query_row("SELECT username FROM users WHERE id=?1", (user.0,), |r| {
+            r.get(0)
+        })?,
+    )
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -69,6 +77,7 @@ mod test {
         let add_user_id = add_user(&mut conn, "test", "test@test.com", "hunter2").unwrap();
         let validate_user_id = validate_user(&mut conn, "test", "hunter2").unwrap();
         assert_eq!(add_user_id, validate_user_id);
+        assert_eq!(get_username(&mut conn, add_user_id).unwrap(), "test");
         assert!(validate_user(&mut conn, "test", "asdas").is_err());
         assert!(validate_user(&mut conn, "asdas", "test").is_err());
     }
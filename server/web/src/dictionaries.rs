@@ -0,0 +1,134 @@
+use std::{collections::HashMap, fs, path::Path, sync::OnceLock};
+
+use wordtwist::words::{Blocklist, Definitions, Dictionary, DEFAULT_DICTIONARY};
+
+#[cfg(test)]
+const DICTIONARY_DIR: &str = "./dictionaries";
+#[cfg(not(test))]
+const DICTIONARY_DIR: &str = "./server/web/dictionaries";
+const FALLBACK_DICTIONARY_DIR: &str = "./web/dictionaries";
+/// The name of the optional file in the dictionary directory listing words to block from every game.
+const BLOCKLIST_FILE: &str = "blocklist";
+/// The name of the optional file in the dictionary directory listing a word and one of its
+/// definitions, separated by a tab, on each line.
+const DEFINITIONS_FILE: &str = "definitions.tsv";
+
+static DICTIONARIES: OnceLock<HashMap<String, Dictionary>> = OnceLock::new();
+
+/// Loads every `<id>.txt` word list in the dictionary directory on first call, returns the
+/// reference on subsequent calls
+fn get_dictionaries() -> &'static HashMap<String, Dictionary> {
+    DICTIONARIES.get_or_init(|| {
+        load_dictionaries(Path::new(DICTIONARY_DIR))
+            .or_else(|_| load_dictionaries(Path::new(FALLBACK_DICTIONARY_DIR)))
+            .unwrap_or_default()
+    })
+}
+
+fn load_dictionaries(dir: &Path) -> std::io::Result<HashMap<String, Dictionary>> {
+    let mut dictionaries = HashMap::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_none_or(|e| e != "txt") {
+            continue;
+        }
+        let Some(id) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        if id == DEFAULT_DICTIONARY {
+            continue;
+        }
+        // multi-letter tiles (like "qu") are listed one per line in an optional `<id>.tiles` file,
+        // and offensive words in an optional `<id>.offensive` file
+        let tiles = fs::read_to_string(path.with_extension("tiles")).unwrap_or_default();
+        let offensive = fs::read_to_string(path.with_extension("offensive")).unwrap_or_default();
+        match Dictionary::from_file(id, &path) {
+            Ok(dictionary) => {
+                let dictionary = dictionary
+                    .with_tiles(tiles.lines())
+                    .with_offensive(offensive.lines());
+                dictionaries.insert(id.to_string(), dictionary);
+            }
+            Err(e) => eprintln!("failed to load dictionary {path:?}: {e}"),
+        }
+    }
+    Ok(dictionaries)
+}
+
+/// Replaces the blocklist compiled into `wordtwist` with the one in the dictionary directory, if
+/// there is one. Must be called before any game is generated.
+pub fn install_blocklist() {
+    let blocklist = Blocklist::from_file(Path::new(DICTIONARY_DIR).join(BLOCKLIST_FILE))
+        .or_else(|_| Blocklist::from_file(Path::new(FALLBACK_DICTIONARY_DIR).join(BLOCKLIST_FILE)));
+    if let Ok(blocklist) = blocklist {
+        if Blocklist::install(blocklist).is_err() {
+            eprintln!("blocklist was already in use, so {BLOCKLIST_FILE} was not installed");
+        }
+    }
+}
+
+/// Installs the definitions in the dictionary directory, if there are any, for words to be looked up
+/// in. Must be called before any definitions are looked up.
+pub fn install_definitions() {
+    let definitions = Definitions::from_file(Path::new(DICTIONARY_DIR).join(DEFINITIONS_FILE))
+        .or_else(|_| {
+            Definitions::from_file(Path::new(FALLBACK_DICTIONARY_DIR).join(DEFINITIONS_FILE))
+        });
+    if let Ok(definitions) = definitions {
+        if Definitions::install(definitions).is_err() {
+            eprintln!("definitions were already in use, so {DEFINITIONS_FILE} was not installed");
+        }
+    }
+}
+
+/// Returns the dictionary registered under `id`, if there is one.
+pub fn get_dictionary(id: &str) -> Option<&'static Dictionary> {
+    if id == DEFAULT_DICTIONARY {
+        return Some(Dictionary::embedded());
+    }
+    get_dictionaries().get(id)
+}
+
+/// Returns the ids of every registered dictionary, in sorted order.
+pub fn get_dictionary_ids() -> Vec<&'static str> {
+    let mut ids: Vec<&str> = get_dictionaries().keys().map(|id| id.as_str()).collect();
+    ids.push(DEFAULT_DICTIONARY);
+    ids.sort_unstable();
+    ids
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn default_dictionary_is_registered() {
+        assert!(get_dictionary(DEFAULT_DICTIONARY).is_some());
+        assert!(get_dictionary_ids().contains(&DEFAULT_DICTIONARY));
+        assert!(get_dictionary("not a dictionary").is_none());
+        // lists shipped in the dictionary directory are registered alongside it
+        assert!(get_dictionary_ids().contains(&"kids"));
+        assert!(get_dictionary("kids")
+            .unwrap()
+            .words()
+            .contains(&"dinosaur".to_string()));
+    }
+
+    #[test]
+    fn load_dictionaries_from_dir() {
+        let dir = std::env::temp_dir().join("wordtwist_load_dictionaries");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("kids.txt"), "cat\ndog\nquack\n").unwrap();
+        fs::write(dir.join("kids.tiles"), "qu\n").unwrap();
+        fs::write(dir.join("kids.offensive"), "dog\n").unwrap();
+        fs::write(dir.join("notes.md"), "not a word list").unwrap();
+
+        let dictionaries = load_dictionaries(&dir).unwrap();
+        assert_eq!(dictionaries.len(), 1);
+        assert_eq!(dictionaries["kids"].words(), ["cat", "dog", "quack"]);
+        assert!(dictionaries["kids"].alphabet().contains(&"qu".to_string()));
+        assert!(dictionaries["kids"].is_offensive("dog"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
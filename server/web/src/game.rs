@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
+use tokio::task;
 use uuid::Uuid;
-use wordtwist::game::Game as GameData;
+use wordtwist::game::{Game as GameData, GameConfig, Scoring};
 
 use crate::db::{
     game::{set_daily, try_get_daily},
@@ -16,9 +17,9 @@ pub struct Game {
 pub struct DailyGame(pub Game);
 
 impl Game {
-    pub fn new(size: usize) -> Self {
+    pub fn new(config: &GameConfig, scoring: Scoring) -> Self {
         let mut conn = open_db_connection();
-        let data = GameData::new(size);
+        let data = GameData::from_config(config).with_scoring(scoring);
 
         let uuid = crate::db::game::insert_game(&mut conn, &data).unwrap();
         Self {
@@ -27,6 +28,14 @@ impl Game {
         }
     }
 
+    /// As [`Game::new`], but on a thread where blocking is fine, since looking for a board that
+    /// meets `config`'s constraints can take many attempts.
+    pub async fn generate(config: GameConfig<'static>, scoring: Scoring) -> Self {
+        task::spawn_blocking(move || Game::new(&config, scoring))
+            .await
+            .unwrap()
+    }
+
     pub fn from(uuid: Uuid, data: GameData) -> Self {
         Self {
             id: uuid.to_string(),
@@ -39,7 +48,7 @@ impl DailyGame {
     pub fn get() -> Self {
         let mut conn = open_db_connection();
         Self(try_get_daily(&mut conn).unwrap_or_else(|_| {
-            let game = Game::new(4);
+            let game = Game::new(&GameConfig::new(4), Scoring::default());
             set_daily(&mut conn, Uuid::parse_str(game.id.as_str()).unwrap())
                 .expect("error adding daily game to db (DailyGame::get)");
             game
@@ -1,5 +1,7 @@
 mod db;
+mod dictionaries;
 mod game;
+mod room;
 mod routes;
 mod ws;
 
@@ -12,9 +14,14 @@ use axum::{
 };
 use axum_extra::extract::cookie::Key;
 use db::open_db_connection;
+use room::Rooms;
 use routes::{
-    game::{get_daily_game, get_existing_game_by_id, get_new_game, get_score, get_stats},
+    game::{
+        get_challenge_game, get_daily_game, get_dictionaries, get_existing_game_by_id, get_lobby,
+        get_new_game, get_new_lobby, get_room_game, get_score, get_stats,
+    },
     user::{create_new_user, get_login, login_user, logout_user},
+    word::get_word,
 };
 use tower_http::services::{ServeDir, ServeFile};
 
@@ -23,6 +30,7 @@ const KEY_BYTES: &[u8] = include_bytes!("../cookie_key");
 #[derive(Clone)]
 struct AppState {
     key: Key,
+    rooms: Rooms,
 }
 
 impl FromRef<AppState> for Key {
@@ -31,12 +39,21 @@ impl FromRef<AppState> for Key {
     }
 }
 
+impl FromRef<AppState> for Rooms {
+    fn from_ref(state: &AppState) -> Self {
+        state.rooms.clone()
+    }
+}
+
 #[tokio::main]
 async fn main() {
     let _ = open_db_connection();
+    dictionaries::install_blocklist();
+    dictionaries::install_definitions();
 
     let state = AppState {
         key: Key::from(KEY_BYTES),
+        rooms: Rooms::default(),
     };
 
     let app = Router::new()
@@ -44,9 +61,15 @@ async fn main() {
         .nest_service("/assets", ServeDir::new("../client/dist/assets"))
         .route("/game/:size", get(get_new_game))
         .route("/game/id/:id", get(get_existing_game_by_id))
+        .route("/game/room/:id", get(get_room_game))
+        .route("/game/challenge/:challenge", get(get_challenge_game))
         .route("/game/daily", get(get_daily_game))
+        .route("/lobby/new/:size", get(get_new_lobby))
+        .route("/lobby/:code", get(get_lobby))
         .route("/game/score/:id", get(get_score))
         .route("/game/stats", get(get_stats))
+        .route("/game/dictionaries", get(get_dictionaries))
+        .route("/word/:word", get(get_word))
         .route("/user", post(create_new_user))
         .route(
             "/login",
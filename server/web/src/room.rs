@@ -0,0 +1,801 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use tokio::{sync::broadcast, time::Instant};
+use uuid::Uuid;
+use wordtwist::game::FoundWord;
+
+use crate::{db::user::UserID, game::Game};
+
+/// How many events a socket can fall behind on before it misses some.
+const EVENT_CAPACITY: usize = 64;
+
+/// How long a lobby waits for its host to start the game before it's closed.
+const LOBBY_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// The characters join codes are made of, leaving out ones that are easily mistaken for others.
+const CODE_CHARACTERS: &[u8] = b"ABCDEFGHJKLMNPQRSTUVWXYZ23456789";
+const CODE_LENGTH: usize = 6;
+
+/// How many teams the players are split into in team mode.
+const TEAM_COUNT: usize = 2;
+
+/// The games being played together right now, by game id, or by join code for lobbies.
+pub type Rooms = Arc<Mutex<HashMap<String, Arc<Room>>>>;
+
+/// How the players in a room play against each other.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RoomMode {
+    /// Everyone can find every word.
+    #[default]
+    Standard,
+    /// Each word can only be claimed by the first player to submit it, and claims are announced to
+    /// everyone.
+    Race,
+    /// Players are split into teams, who share the words they find and their score.
+    Teams,
+}
+
+/// A session of one game, shared by every socket playing it together, with a single timer kept by
+/// the server.
+pub struct Room {
+    pub(crate) game: Game,
+    /// The length of the game, in seconds.
+    pub(crate) time: u64,
+    /// The code players join the room with, if it was opened as a lobby.
+    pub(crate) code: Option<String>,
+    pub(crate) mode: RoomMode,
+    state: Mutex<RoomState>,
+    events: broadcast::Sender<RoomEvent>,
+}
+
+struct RoomState {
+    players: Vec<Player>,
+    /// When the timer runs out, or `None` if the game hasn't started yet.
+    deadline: Option<Instant>,
+    over: bool,
+    /// The player who claimed each word, in race mode.
+    claims: HashMap<String, usize>,
+    /// Every team's words and score, in team mode.
+    teams: Vec<Team>,
+}
+
+/// A team's pooled words and score, in team mode.
+#[derive(Default)]
+struct Team {
+    words: Vec<FoundWord>,
+    score: usize,
+}
+
+/// Someone playing in a room.
+struct Player {
+    name: String,
+    user: Option<UserID>,
+    words: Vec<FoundWord>,
+    score: usize,
+    /// Whether the player left the lobby before the game started.
+    left: bool,
+    /// The team the player is on, in team mode.
+    team: Option<usize>,
+}
+
+/// Something that happened in a room, sent to every socket in it.
+#[derive(Debug, Clone)]
+pub enum RoomEvent {
+    Joined {
+        player: usize,
+        name: String,
+        team: Option<usize>,
+    },
+    /// A player left the lobby before the game started.
+    Left { player: usize, name: String },
+    /// The timer started, and guesses are accepted until it runs out.
+    Started,
+    /// A player found a word, bringing them to `words` words worth `score` points.
+    Found {
+        player: usize,
+        words: usize,
+        score: usize,
+    },
+    /// A player found `word` for their team in team mode, worth `score` points.
+    TeamFound {
+        team: usize,
+        player: usize,
+        word: String,
+        score: usize,
+    },
+    /// A player claimed `word` in race mode, so nobody else can find it.
+    Claimed { player: usize, word: String },
+    /// The timer ran out, or the lobby was closed without the game being started.
+    Over,
+}
+
+/// A team's result in team mode, as shown once the game is over.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamStanding {
+    pub team: usize,
+    pub players: Vec<String>,
+    pub words: usize,
+    pub score: usize,
+}
+
+/// What came of a guess in a room.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Guess<E> {
+    Accepted {
+        word: String,
+        score: usize,
+    },
+    /// The guess was rejected by the check it was given.
+    Rejected(E),
+    /// The word is valid, but someone else already claimed it in race mode.
+    Claimed {
+        word: String,
+        by: String,
+    },
+}
+
+/// A player's result in a room, as shown in the standings once the game is over.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Standing {
+    pub name: String,
+    pub words: usize,
+    pub score: usize,
+}
+
+impl Room {
+    fn new(game: Game, time: u64, code: Option<String>, mode: RoomMode) -> Self {
+        Room {
+            game,
+            time,
+            code,
+            mode,
+            state: Mutex::new(RoomState {
+                players: Vec::new(),
+                deadline: None,
+                over: false,
+                claims: HashMap::new(),
+                teams: match mode {
+                    RoomMode::Teams => (0..TEAM_COUNT).map(|_| Team::default()).collect(),
+                    _ => Vec::new(),
+                },
+            }),
+            events: broadcast::channel(EVENT_CAPACITY).0,
+        }
+    }
+
+    /// Returns the room playing the game with `id`, or the lobby with the join code `id`, if there
+    /// is one.
+    pub fn get(rooms: &Rooms, id: &str) -> Option<Arc<Room>> {
+        rooms.lock().unwrap().get(id).cloned()
+    }
+
+    /// Opens a room playing `game` for `time` seconds in `mode`, starting its timer, or returns the
+    /// room already playing it.
+    ///
+    /// The room closes itself when the timer runs out, and a later join opens a new one. Returns
+    /// `None` if the timer can't be started, in which case no room is opened.
+    pub fn open(rooms: &Rooms, game: Game, time: u64, mode: RoomMode) -> Option<Arc<Room>> {
+        let mut open_rooms = rooms.lock().unwrap();
+        if let Some(room) = open_rooms.get(&game.id) {
+            return Some(room.clone());
+        }
+        let room = Arc::new(Room::new(game, time, None, mode));
+        // the timer closing the room waits on the lock, so it can't run before the room is added
+        if !room.start(rooms) {
+            return None;
+        }
+        open_rooms.insert(room.game.id.clone(), room.clone());
+        Some(room)
+    }
+
+    /// Opens a lobby for `game` in `mode` under a new join code, where players wait until the host,
+    /// who is the first to join, starts the game for everyone at once.
+    ///
+    /// The lobby is closed if the game isn't started within [`LOBBY_TIMEOUT`]. Returns `None` if
+    /// the time is too long for a timer to run out, as the host could never start the game.
+    pub fn open_lobby(rooms: &Rooms, game: Game, time: u64, mode: RoomMode) -> Option<Arc<Room>> {
+        deadline(time)?;
+        let room = {
+            let mut open_rooms = rooms.lock().unwrap();
+            let code = loop {
+                let code = new_code();
+                if !open_rooms.contains_key(&code) {
+                    break code;
+                }
+            };
+            let room = Arc::new(Room::new(game, time, Some(code.clone()), mode));
+            open_rooms.insert(code, room.clone());
+            room
+        };
+
+        let (rooms, lobby) = (rooms.clone(), room.clone());
+        tokio::spawn(async move {
+            tokio::time::sleep(LOBBY_TIMEOUT).await;
+            let mut state = lobby.state.lock().unwrap();
+            if state.deadline.is_none() {
+                state.over = true;
+                drop(state);
+                lobby.close(&rooms);
+            }
+        });
+        Some(room)
+    }
+
+    /// Starts the room's timer, returning `false` if it had already been started, or if the time
+    /// is too long for the timer to run out.
+    pub fn start(self: &Arc<Self>, rooms: &Rooms) -> bool {
+        let Some(deadline) = deadline(self.time) else {
+            return false;
+        };
+        {
+            let mut state = self.state.lock().unwrap();
+            if state.deadline.is_some() || state.over {
+                return false;
+            }
+            state.deadline = Some(deadline);
+        }
+        let _ = self.events.send(RoomEvent::Started);
+
+        let (rooms, room) = (rooms.clone(), self.clone());
+        tokio::spawn(async move {
+            tokio::time::sleep_until(deadline).await;
+            room.state.lock().unwrap().over = true;
+            room.close(&rooms);
+        });
+        true
+    }
+
+    /// Takes the room out of `rooms`, and tells everyone in it that it's over.
+    fn close(&self, rooms: &Rooms) {
+        let key = self.code.as_ref().unwrap_or(&self.game.id);
+        rooms.lock().unwrap().remove(key);
+        let _ = self.events.send(RoomEvent::Over);
+    }
+
+    /// Returns whether the room's timer has been started.
+    pub fn is_started(&self) -> bool {
+        self.state.lock().unwrap().deadline.is_some()
+    }
+
+    /// Adds a player called `name` to the room, returning their index and a receiver of the room's
+    /// events from now on.
+    ///
+    /// Players are told apart by name, so a number is added to names that are already taken. In
+    /// team mode, players are put on the team with the fewest players.
+    pub fn join(
+        &self,
+        name: String,
+        user: Option<UserID>,
+    ) -> (usize, broadcast::Receiver<RoomEvent>) {
+        let events = self.events.subscribe();
+        let mut state = self.state.lock().unwrap();
+        let player = state.players.len();
+        let taken = |name: &str| state.players.iter().any(|p| !p.left && p.name == name);
+        let name = if taken(&name) {
+            (2..)
+                .map(|n| format!("{name} {n}"))
+                .find(|n| !taken(n))
+                .unwrap()
+        } else {
+            name
+        };
+        let team = (0..state.teams.len()).min_by_key(|&team| {
+            state
+                .players
+                .iter()
+                .filter(|p| !p.left && p.team == Some(team))
+                .count()
+        });
+        state.players.push(Player {
+            name: name.clone(),
+            user,
+            words: Vec::new(),
+            score: 0,
+            left: false,
+            team,
+        });
+        let _ = self.events.send(RoomEvent::Joined { player, name, team });
+        (player, events)
+    }
+
+    /// Takes `player` out of the room, if the game hasn't started yet.
+    ///
+    /// Players who leave once the game has started keep their place in the standings.
+    pub fn leave(&self, player: usize) {
+        let mut state = self.state.lock().unwrap();
+        if state.deadline.is_some() || state.players[player].left {
+            return;
+        }
+        state.players[player].left = true;
+        let name = state.players[player].name.clone();
+        let _ = self.events.send(RoomEvent::Left { player, name });
+    }
+
+    /// Returns the host of the room, who can start the game: the first player to join who hasn't
+    /// left.
+    pub fn host(&self) -> Option<usize> {
+        let state = self.state.lock().unwrap();
+        state.players.iter().position(|p| !p.left)
+    }
+
+    /// Returns the names of the players in the room, in the order they joined.
+    pub fn players(&self) -> Vec<String> {
+        let state = self.state.lock().unwrap();
+        state
+            .players
+            .iter()
+            .filter(|p| !p.left)
+            .map(|p| p.name.clone())
+            .collect()
+    }
+
+    /// Returns how many seconds are left on the room's timer, which is all of them if it hasn't
+    /// started yet.
+    pub fn remaining_time(&self) -> u64 {
+        match self.state.lock().unwrap().deadline {
+            Some(deadline) => deadline.saturating_duration_since(Instant::now()).as_secs(),
+            None => self.time,
+        }
+    }
+
+    /// Checks a guess by `player` with `check`, given the words they've found so far, then records
+    /// the word it accepts and tells the room about it.
+    ///
+    /// In team mode, `check` is given every word the player's team has found, and the word it
+    /// accepts counts for the whole team.
+    ///
+    /// In race mode, a word someone else already claimed isn't accepted. Guesses are handled one
+    /// at a time, so when two players submit the same word, the first one received claims it.
+    ///
+    /// Returns `None` if the game isn't being played.
+    pub fn guess<E>(
+        &self,
+        player: usize,
+        check: impl FnOnce(&[FoundWord]) -> Result<FoundWord, E>,
+    ) -> Option<Guess<E>> {
+        let mut state = self.state.lock().unwrap();
+        if state.over || state.deadline.is_none() {
+            return None;
+        }
+        let team = state.players[player].team;
+        let found_words = match team {
+            Some(team) => &state.teams[team].words,
+            None => &state.players[player].words,
+        };
+        let found = match check(found_words) {
+            Ok(found) => found,
+            Err(e) => return Some(Guess::Rejected(e)),
+        };
+        let word = found.word.clone();
+        if self.mode == RoomMode::Race {
+            if let Some(&claimant) = state.claims.get(&word) {
+                let by = state.players[claimant].name.clone();
+                return Some(Guess::Claimed { word, by });
+            }
+            state.claims.insert(word.clone(), player);
+            let _ = self.events.send(RoomEvent::Claimed {
+                player,
+                word: word.clone(),
+            });
+        }
+        let score = self.game.data.score_word(&word, found.path.as_deref());
+        if let Some(team) = team {
+            state.teams[team].words.push(found.clone());
+            state.teams[team].score += score;
+            let _ = self.events.send(RoomEvent::TeamFound {
+                team,
+                player,
+                word: word.clone(),
+                score,
+            });
+        }
+        let finder = &mut state.players[player];
+        finder.words.push(found);
+        finder.score += score;
+        let _ = self.events.send(RoomEvent::Found {
+            player,
+            words: finder.words.len(),
+            score: finder.score,
+        });
+        Some(Guess::Accepted { word, score })
+    }
+
+    /// Returns the words `player` has found.
+    ///
+    /// In team mode, these are the words the player's whole team has found.
+    pub fn words(&self, player: usize) -> Vec<FoundWord> {
+        let state = self.state.lock().unwrap();
+        match state.players[player].team {
+            Some(team) => state.teams[team].words.clone(),
+            None => state.players[player].words.clone(),
+        }
+    }
+
+    /// Returns the words `player` has found themselves, even in team mode.
+    pub fn own_words(&self, player: usize) -> Vec<FoundWord> {
+        self.state.lock().unwrap().players[player].words.clone()
+    }
+
+    /// Returns the points `player` has scored on their own, even in team mode.
+    pub fn score(&self, player: usize) -> usize {
+        self.state.lock().unwrap().players[player].score
+    }
+
+    /// Returns the team `player` is on, in team mode.
+    pub fn team(&self, player: usize) -> Option<usize> {
+        self.state.lock().unwrap().players[player].team
+    }
+
+    /// Returns the names of the other players on `player`'s team, in team mode.
+    pub fn teammates(&self, player: usize) -> Vec<String> {
+        let state = self.state.lock().unwrap();
+        let team = state.players[player].team;
+        state
+            .players
+            .iter()
+            .enumerate()
+            .filter(|&(i, p)| i != player && !p.left && team.is_some() && p.team == team)
+            .map(|(_, p)| p.name.clone())
+            .collect()
+    }
+
+    /// Returns the name of `player`.
+    pub fn name(&self, player: usize) -> String {
+        self.state.lock().unwrap().players[player].name.clone()
+    }
+
+    /// Returns whether `user` is already playing in the room.
+    pub fn has_user(&self, user: UserID) -> bool {
+        let state = self.state.lock().unwrap();
+        state
+            .players
+            .iter()
+            .any(|p| !p.left && p.user == Some(user))
+    }
+
+    /// Returns every player's result, best first.
+    pub fn standings(&self) -> Vec<Standing> {
+        let state = self.state.lock().unwrap();
+        let mut standings: Vec<Standing> = state
+            .players
+            .iter()
+            .filter(|p| !p.left)
+            .map(|p| Standing {
+                name: p.name.clone(),
+                words: p.words.len(),
+                score: p.score,
+            })
+            .collect();
+        standings.sort_by(|a, b| b.score.cmp(&a.score).then(a.name.cmp(&b.name)));
+        standings
+    }
+
+    /// Returns every team's result, best first, or `None` if the room isn't in team mode.
+    pub fn team_standings(&self) -> Option<Vec<TeamStanding>> {
+        if self.mode != RoomMode::Teams {
+            return None;
+        }
+        let state = self.state.lock().unwrap();
+        let mut standings: Vec<TeamStanding> = state
+            .teams
+            .iter()
+            .enumerate()
+            .map(|(i, team)| TeamStanding {
+                team: i,
+                players: state
+                    .players
+                    .iter()
+                    .filter(|p| !p.left && p.team == Some(i))
+                    .map(|p| p.name.clone())
+                    .collect(),
+                words: team.words.len(),
+                score: team.score,
+            })
+            .collect();
+        standings.sort_by(|a, b| b.score.cmp(&a.score).then(a.team.cmp(&b.team)));
+        Some(standings)
+    }
+}
+
+/// Returns a random join code of [`CODE_LENGTH`] characters.
+fn new_code() -> String {
+    Uuid::new_v4().as_bytes()[..CODE_LENGTH]
+        .iter()
+        .map(|b| CODE_CHARACTERS[*b as usize % CODE_CHARACTERS.len()] as char)
+        .collect()
+}
+
+/// Returns when a timer started now for `time` seconds runs out, or `None` if that's too far off
+/// to be counted.
+fn deadline(time: u64) -> Option<Instant> {
+    Instant::now().checked_add(Duration::from_secs(time))
+}
+
+#[cfg(test)]
+mod test {
+    use wordtwist::game::{Game as GameData, GameConfig};
+
+    use super::*;
+
+    fn game() -> Game {
+        Game::from(
+            uuid::Uuid::new_v4(),
+            GameData::generate_from_seed(&GameConfig::new(4), 1),
+        )
+    }
+
+    #[tokio::test]
+    async fn players_share_a_room() {
+        let rooms = Rooms::default();
+        let room = Room::open(&rooms, game(), 60, RoomMode::Standard).unwrap();
+        assert!(Arc::ptr_eq(
+            &Room::get(&rooms, &room.game.id).unwrap(),
+            &room
+        ));
+        assert!(Arc::ptr_eq(
+            &Room::open(&rooms, game_with_id(&room.game.id), 60, RoomMode::Race).unwrap(),
+            &room
+        ));
+
+        let (alice, mut alice_events) = room.join("alice".to_string(), None);
+        let (bob, _) = room.join("bob".to_string(), Some(UserID(2)));
+        let (other_bob, _) = room.join("bob".to_string(), None);
+        assert_eq!(room.name(other_bob), "bob 2");
+        assert!(matches!(
+            alice_events.recv().await.unwrap(),
+            RoomEvent::Joined { player: 0, .. }
+        ));
+        assert!(matches!(
+            alice_events.recv().await.unwrap(),
+            RoomEvent::Joined { player: 1, .. }
+        ));
+        assert!(matches!(
+            alice_events.recv().await.unwrap(),
+            RoomEvent::Joined { player: 2, name, team: None } if name == "bob 2"
+        ));
+        assert!(room.has_user(UserID(2)));
+        assert!(room.remaining_time() <= 60);
+
+        let word = room.game.data.valid_words()[0].clone();
+        let accept = |words: &[FoundWord]| {
+            if words.iter().any(|found| found.word == word) {
+                Err(())
+            } else {
+                Ok(FoundWord::from(word.clone()))
+            }
+        };
+        let score = room.game.data.score_word(&word, None);
+        assert_eq!(
+            room.guess(bob, accept),
+            Some(Guess::Accepted {
+                word: word.clone(),
+                score
+            })
+        );
+        assert_eq!(room.guess(bob, accept), Some(Guess::Rejected(())));
+        assert!(matches!(
+            alice_events.recv().await.unwrap(),
+            RoomEvent::Found { player: 1, words: 1, score: s } if s == score
+        ));
+        assert_eq!(room.words(bob), [FoundWord::from(word)]);
+        assert!(room.words(alice).is_empty());
+        assert_eq!(
+            room.standings(),
+            [
+                Standing {
+                    name: "bob".to_string(),
+                    words: 1,
+                    score
+                },
+                Standing {
+                    name: "alice".to_string(),
+                    words: 0,
+                    score: 0
+                },
+                Standing {
+                    name: "bob 2".to_string(),
+                    words: 0,
+                    score: 0
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn rooms_close_when_time_runs_out() {
+        let rooms = Rooms::default();
+        let room = Room::open(&rooms, game(), 0, RoomMode::Standard).unwrap();
+        let (player, mut events) = room.join("alice".to_string(), None);
+        let _ = events.recv().await;
+
+        assert!(matches!(events.recv().await.unwrap(), RoomEvent::Over));
+        assert!(Room::get(&rooms, &room.game.id).is_none());
+        assert!(room
+            .guess(player, |_| Ok::<_, ()>(FoundWord::from("late".to_string())))
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn rooms_that_cant_start_arent_opened() {
+        let rooms = Rooms::default();
+        assert!(Room::open(&rooms, game(), u64::MAX, RoomMode::Standard).is_none());
+        assert!(rooms.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn words_are_claimed_in_race_mode() {
+        let rooms = Rooms::default();
+        let room = Room::open(&rooms, game(), 60, RoomMode::Race).unwrap();
+        let (alice, mut events) = room.join("alice".to_string(), None);
+        let (bob, _) = room.join("bob".to_string(), None);
+
+        let word = room.game.data.valid_words()[0].clone();
+        let score = room.game.data.score_word(&word, None);
+        let accept = |_: &[FoundWord]| Ok::<_, ()>(FoundWord::from(word.clone()));
+        assert_eq!(
+            room.guess(alice, accept),
+            Some(Guess::Accepted {
+                word: word.clone(),
+                score
+            })
+        );
+        assert_eq!(
+            room.guess(bob, accept),
+            Some(Guess::Claimed {
+                word: word.clone(),
+                by: "alice".to_string()
+            })
+        );
+        assert!(room.words(bob).is_empty());
+
+        let mut received = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            received.push(event);
+        }
+        assert!(matches!(
+            received.as_slice(),
+            [
+                RoomEvent::Joined { .. },
+                RoomEvent::Joined { .. },
+                RoomEvent::Claimed { player: 0, word: claimed },
+                RoomEvent::Found { player: 0, words: 1, .. },
+            ] if *claimed == word
+        ));
+    }
+
+    #[tokio::test]
+    async fn teams_pool_their_words() {
+        let rooms = Rooms::default();
+        let room = Room::open(&rooms, game(), 60, RoomMode::Teams).unwrap();
+        let (alice, mut events) = room.join("alice".to_string(), None);
+        let (bob, _) = room.join("bob".to_string(), None);
+        let (carol, _) = room.join("carol".to_string(), None);
+        assert_eq!(room.team(alice), Some(0));
+        assert_eq!(room.team(bob), Some(1));
+        assert_eq!(room.team(carol), Some(0));
+        assert_eq!(room.teammates(alice), ["carol"]);
+        assert!(room.teammates(bob).is_empty());
+
+        let word = room.game.data.valid_words()[0].clone();
+        let score = room.game.data.score_word(&word, None);
+        let accept = |words: &[FoundWord]| {
+            if words.iter().any(|found| found.word == word) {
+                Err(())
+            } else {
+                Ok(FoundWord::from(word.clone()))
+            }
+        };
+        assert!(matches!(
+            room.guess(alice, accept),
+            Some(Guess::Accepted { .. })
+        ));
+        // teammates share their words, but the other team can still find them
+        assert_eq!(room.guess(carol, accept), Some(Guess::Rejected(())));
+        assert!(matches!(
+            room.guess(bob, accept),
+            Some(Guess::Accepted { .. })
+        ));
+        assert_eq!(room.words(carol), [FoundWord::from(word.clone())]);
+        assert!(room.own_words(carol).is_empty());
+        assert_eq!(room.own_words(alice), [FoundWord::from(word.clone())]);
+        assert_eq!((room.score(alice), room.score(carol)), (score, 0));
+
+        let mut received = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            received.push(event);
+        }
+        assert!(matches!(
+            &received[3..5],
+            [
+                RoomEvent::TeamFound { team: 0, player: 0, word: found, .. },
+                RoomEvent::Found { player: 0, .. },
+            ] if *found == word
+        ));
+        assert_eq!(
+            room.team_standings().unwrap(),
+            [
+                TeamStanding {
+                    team: 0,
+                    players: vec!["alice".to_string(), "carol".to_string()],
+                    words: 1,
+                    score
+                },
+                TeamStanding {
+                    team: 1,
+                    players: vec!["bob".to_string()],
+                    words: 1,
+                    score
+                },
+            ]
+        );
+        assert!(Room::open(&rooms, game(), 60, RoomMode::Standard)
+            .unwrap()
+            .team_standings()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn lobbies_wait_for_the_host() {
+        let rooms = Rooms::default();
+        assert!(Room::open_lobby(&rooms, game(), u64::MAX, RoomMode::Standard).is_none());
+        assert!(rooms.lock().unwrap().is_empty());
+        let room = Room::open_lobby(&rooms, game(), 60, RoomMode::Standard).unwrap();
+        let code = room.code.clone().unwrap();
+        assert_eq!(code.len(), CODE_LENGTH);
+        assert!(code.bytes().all(|c| CODE_CHARACTERS.contains(&c)));
+        assert!(Arc::ptr_eq(&Room::get(&rooms, &code).unwrap(), &room));
+        assert!(Room::get(&rooms, &room.game.id).is_none());
+
+        let (host, mut events) = room.join("host".to_string(), None);
+        let (guest, _) = room.join("guest".to_string(), None);
+        let (other, _) = room.join("other".to_string(), None);
+        assert_eq!(room.host(), Some(host));
+        assert!(!room.is_started());
+        assert_eq!(room.remaining_time(), 60);
+        assert!(room
+            .guess(guest, |_| Ok::<_, ()>(FoundWord::from("early".to_string())))
+            .is_none());
+
+        room.leave(other);
+        assert_eq!(room.players(), ["host", "guest"]);
+        room.leave(host);
+        assert_eq!(room.host(), Some(guest));
+
+        assert!(room.start(&rooms));
+        assert!(!room.start(&rooms));
+        assert!(room.is_started());
+        room.leave(guest);
+        assert_eq!(room.players(), ["guest"]);
+
+        let mut received = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            received.push(event);
+        }
+        assert!(matches!(
+            received.as_slice(),
+            [
+                RoomEvent::Joined { player: 0, .. },
+                RoomEvent::Joined { player: 1, .. },
+                RoomEvent::Joined { player: 2, .. },
+                RoomEvent::Left { player: 2, .. },
+                RoomEvent::Left { player: 0, .. },
+                RoomEvent::Started,
+            ]
+        ));
+    }
+
+    fn game_with_id(id: &str) -> Game {
+        Game {
+            id: id.to_string(),
+            ..game()
+        }
+    }
+}
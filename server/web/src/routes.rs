@@ -1,2 +1,3 @@
 pub mod game;
 pub mod user;
+pub mod word;
@@ -2,22 +2,31 @@ use std::net::SocketAddr;
 
 use crate::{
     db::{
-        game::{get_game_by_id, get_game_score, get_game_stats},
+        game::{get_game_by_id, get_game_score, get_game_stats, get_score_by_challenge},
         open_db_connection,
+        user::{get_username, UserID},
     },
+    dictionaries::{get_dictionary, get_dictionary_ids},
     game::{DailyGame, Game},
-    ws::handle_socket_game,
+    room::{Room, RoomMode, Rooms},
+    ws::{handle_socket_game, handle_socket_lobby, handle_socket_room, SocketOptions},
 };
 
 use axum::{
-    extract::{ConnectInfo, Path, Query, WebSocketUpgrade},
+    extract::{ConnectInfo, Path, Query, State, WebSocketUpgrade},
     http::StatusCode,
     response::IntoResponse,
     Json,
 };
 use axum_extra::extract::SignedCookieJar;
-use serde::Deserialize;
+use serde::{de, Deserialize, Deserializer};
 use uuid::Uuid;
+use wordtwist::{
+    game::{
+        BoardConstraints, Difficulty, GameConfig, GameRules, Mask, ModifierDensity, Scoring, Shape,
+    },
+    words::{LetterDistribution, LetterWeights, Topology, DEFAULT_DICTIONARY},
+};
 
 use super::user::get_uid_from_cookie;
 
@@ -27,18 +36,112 @@ struct GetGameStatsDTO {
     max_time: usize,
 }
 
+/// Options for generating a new game, provided as query parameters.
+#[derive(Deserialize)]
+pub struct NewGameOptions {
+    #[serde(default)]
+    scoring: Scoring,
+    #[serde(default = "default_dictionary")]
+    dictionary: String,
+    #[serde(default)]
+    letters: LetterDistribution,
+    /// A table of tiles to weight the letters by, like `e:12,t:9,qu:1`, in place of `letters`.
+    weights: Option<LetterWeights>,
+    difficulty: Option<Difficulty>,
+    /// Requirements the board must meet, checked against the board's stats.
+    #[serde(default)]
+    min_words: usize,
+    max_words: Option<usize>,
+    #[serde(default)]
+    min_long_words: usize,
+    max_repeated_tile: Option<usize>,
+    /// How many boards to generate looking for one that meets the requirements, if not the default.
+    max_attempts: Option<usize>,
+    /// The number of columns, if different from the number of rows given in the path.
+    cols: Option<usize>,
+    #[serde(default)]
+    mask: Mask,
+    /// Squares to leave out of the board, as `y,x` pairs separated by semicolons, like `0,0;2,3`,
+    /// in place of `mask`.
+    holes: Option<String>,
+    #[serde(default)]
+    topology: Topology,
+    /// The share of squares that are double letter, triple word, blocked or blank squares.
+    #[serde(default)]
+    double_letter: f64,
+    #[serde(default)]
+    triple_word: f64,
+    #[serde(default)]
+    blocked: f64,
+    #[serde(default)]
+    wildcard: f64,
+    /// The fewest letters a word can have, if not the default.
+    min_word_length: Option<usize>,
+    #[serde(default)]
+    exclude_plurals: bool,
+    #[serde(default)]
+    exclude_proper_nouns: bool,
+    #[serde(default)]
+    exclude_offensive: bool,
+    #[serde(default)]
+    reuse_cells: bool,
+}
+
+/// The largest share of a new game's squares that can be blank.
+const MAX_WILDCARD_DENSITY: f64 = 0.2;
+
+/// The most boards a new game can ask to be generated while looking for one meeting its
+/// requirements.
+const MAX_ATTEMPTS: usize = 100;
+
+/// The most characters a guest's name can have in a room.
+const MAX_NAME_LENGTH: usize = 20;
+
+/// The most seconds a game can be played for.
+const MAX_GAME_TIME: u64 = 24 * 60 * 60;
+
+/// Options for joining a room, provided as query parameters.
+#[derive(Deserialize)]
+pub struct RoomOptions {
+    /// The name to show to other players, for players who aren't logged in.
+    name: Option<String>,
+    /// How the players play against each other, if this opens a new room.
+    #[serde(default)]
+    mode: RoomMode,
+}
+
+fn default_dictionary() -> String {
+    DEFAULT_DICTIONARY.to_string()
+}
+
+/// How many seconds a game is played for, provided as a query parameter.
+///
+/// A time longer than [`MAX_GAME_TIME`] is rejected, like a malformed one, so the game is played
+/// for the default time instead.
 #[derive(Deserialize)]
 pub struct GameTime {
+    #[serde(deserialize_with = "bounded_time")]
     time: u64,
 }
 
+fn bounded_time<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u64, D::Error> {
+    let time = u64::deserialize(deserializer)?;
+    if time > MAX_GAME_TIME {
+        return Err(de::Error::custom("game time is too long"));
+    }
+    Ok(time)
+}
+
 impl GameTime {
-    fn from_game_size(size: usize) -> Option<Self> {
-        if !(3..=7).contains(&size) {
+    /// Returns the default time for a board of `shape`, which is the time for a square board with
+    /// about as many squares.
+    fn from_shape(shape: &Shape) -> Option<Self> {
+        if !(3..=7).contains(&shape.rows) || !(3..=7).contains(&shape.cols) {
             return None;
         }
+        let size = ((shape.len() as f64).sqrt().round() as u64).max(3);
         Some(GameTime {
-            time: (size as u64 - 2) * 60,
+            time: (size - 2) * 60,
         })
     }
 }
@@ -49,65 +152,323 @@ impl Default for GameTime {
     }
 }
 
+impl NewGameOptions {
+    /// Returns the config for a new game with `size` rows and these options, along with its
+    /// default time, or the error to respond with if the options are invalid.
+    fn config(
+        &self,
+        size: usize,
+    ) -> Result<(GameConfig<'static>, GameTime), (StatusCode, &'static str)> {
+        let mask = match &self.holes {
+            Some(holes) => match Mask::parse_holes(holes) {
+                Some(mask) => mask,
+                None => return Err((StatusCode::BAD_REQUEST, "Invalid holes")),
+            },
+            None => self.mask.clone(),
+        };
+        let shape = Shape {
+            rows: size,
+            cols: self.cols.unwrap_or(size),
+            mask,
+        };
+        let Some(default_time) = GameTime::from_shape(&shape) else {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                "Invalid game size. Games can have 3-7 rows and columns inclusive.",
+            ));
+        };
+        // like blocked squares, holes must leave at least half the board to play on
+        if 2 * shape.len() < shape.rows * shape.cols {
+            return Err((StatusCode::BAD_REQUEST, "Invalid holes"));
+        }
+        let Some(dictionary) = get_dictionary(&self.dictionary) else {
+            return Err((StatusCode::BAD_REQUEST, "Unknown dictionary"));
+        };
+        let modifiers = ModifierDensity {
+            double_letter: self.double_letter,
+            triple_word: self.triple_word,
+            blocked: self.blocked,
+            wildcard: self.wildcard,
+        };
+        let densities = [
+            modifiers.double_letter,
+            modifiers.triple_word,
+            modifiers.blocked,
+            modifiers.wildcard,
+        ];
+        // leave at least half the board free of blocked squares, so there are still words to find,
+        // and few enough blanks that the board can be solved quickly
+        if densities.iter().any(|d| !(0.0..=1.0).contains(d))
+            || densities.iter().sum::<f64>() > 1.0
+            || modifiers.blocked > 0.5
+            || modifiers.wildcard > MAX_WILDCARD_DENSITY
+        {
+            return Err((StatusCode::BAD_REQUEST, "Invalid special square densities"));
+        }
+        let rules = GameRules {
+            min_word_length: self
+                .min_word_length
+                .unwrap_or(GameRules::default().min_word_length),
+            exclude_plurals: self.exclude_plurals,
+            exclude_proper_nouns: self.exclude_proper_nouns,
+            exclude_offensive: self.exclude_offensive,
+            reuse_cells: self.reuse_cells,
+        };
+        let constraints = BoardConstraints {
+            min_words: self.min_words,
+            max_words: self.max_words,
+            min_long_words: self.min_long_words,
+            max_repeated_tile: self.max_repeated_tile,
+            difficulty: self.difficulty,
+            max_attempts: self
+                .max_attempts
+                .unwrap_or(BoardConstraints::default().max_attempts),
+        };
+        if constraints
+            .max_words
+            .is_some_and(|max| max < constraints.min_words)
+            || constraints.max_repeated_tile == Some(0)
+            || !(1..=MAX_ATTEMPTS).contains(&constraints.max_attempts)
+        {
+            return Err((StatusCode::BAD_REQUEST, "Invalid board constraints"));
+        }
+        let config = GameConfig {
+            shape,
+            topology: self.topology,
+            modifiers,
+            dictionary,
+            distribution: match &self.weights {
+                Some(weights) => LetterDistribution::Weights(weights.clone()),
+                None => self.letters.clone(),
+            },
+            constraints,
+            rules,
+        };
+        if config.rules.min_word_length == 0 {
+            return Err((StatusCode::BAD_REQUEST, "Invalid minimum word length"));
+        }
+        // holes can split the board into regions too small to hide a word in, with a square to spare
+        if config.shape.largest_region(config.topology) <= config.rules.min_word_length {
+            return Err((StatusCode::BAD_REQUEST, "Invalid holes"));
+        }
+        // the hidden target word has to be one the rules allow, and short enough to fit the board
+        if !config.has_target_words() {
+            return Err((StatusCode::BAD_REQUEST, "Invalid minimum word length"));
+        }
+        Ok((config, default_time))
+    }
+}
+
 pub async fn get_new_game(
     Path(size): Path<usize>,
     time: Option<Query<GameTime>>,
+    Query(game_options): Query<NewGameOptions>,
+    Query(options): Query<SocketOptions>,
     jar: SignedCookieJar,
     ws: WebSocketUpgrade,
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
 ) -> impl IntoResponse {
-    if !(3..=7).contains(&size) {
-        return Err::<(), _>((
-            StatusCode::BAD_REQUEST,
-            "Invalid game size. Games can be of size 3-7 inclusive.",
-        ))
-        .into_response();
-    }
-    let Query(time) = time.unwrap_or_else(|| Query(GameTime::from_game_size(size).unwrap()));
+    let (config, default_time) = match game_options.config(size) {
+        Ok(config) => config,
+        Err(e) => return Err::<(), _>(e).into_response(),
+    };
+    let Query(time) = time.unwrap_or(Query(default_time));
     let user = get_uid_from_cookie(jar);
-    ws.on_upgrade(move |socket| handle_socket_game(socket, addr, Game::new(size), time.time, user))
-        .into_response()
+    ws.on_upgrade(move |socket| async move {
+        let game = Game::generate(config, game_options.scoring).await;
+        handle_socket_game(socket, addr, game, time.time, user, options, None).await
+    })
+    .into_response()
+}
+
+/// Fetches the game with `id` from the database, or the error to respond with if it can't be.
+fn fetch_game(id: &str) -> Result<Game, (StatusCode, &'static str)> {
+    let conn = &mut open_db_connection();
+    let Ok(id) = Uuid::parse_str(id) else {
+        return Err((StatusCode::BAD_REQUEST, "Cannot parse provided id"));
+    };
+    match get_game_by_id(conn, id) {
+        Ok(game) => Ok(Game::from(id, game)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => {
+            Err((StatusCode::NOT_FOUND, "Game with specified ID not found"))
+        }
+        Err(_) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Error fetching game from database",
+        )),
+    }
 }
 
 pub async fn get_existing_game_by_id(
     Path(id): Path<String>,
     time: Option<Query<GameTime>>,
+    Query(options): Query<SocketOptions>,
     jar: SignedCookieJar,
     ws: WebSocketUpgrade,
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
 ) -> impl IntoResponse {
-    let conn = &mut open_db_connection();
-    let id = match Uuid::parse_str(&id) {
-        Ok(id) => id,
-        Err(_) => {
-            return Err::<(), _>((StatusCode::BAD_REQUEST, "Cannot parse provided id"))
-                .into_response()
-        }
-    };
-    let game_data = match get_game_by_id(conn, id) {
+    let game = match fetch_game(&id) {
         Ok(game) => game,
+        Err(e) => return Err::<(), _>(e).into_response(),
+    };
+    let Query(time) =
+        time.unwrap_or_else(|| Query(GameTime::from_shape(&game.data.shape()).unwrap()));
+    let user = get_uid_from_cookie(jar);
+    ws.on_upgrade(move |socket| {
+        handle_socket_game(socket, addr, game, time.time, user, options, None)
+    })
+    .into_response()
+}
+
+/// Plays the game from the score with the `challenge` token against the same clock, then compares
+/// the words found with those of the player who set the score.
+pub async fn get_challenge_game(
+    Path(challenge): Path<String>,
+    Query(options): Query<SocketOptions>,
+    jar: SignedCookieJar,
+    ws: WebSocketUpgrade,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+) -> impl IntoResponse {
+    let challenge = match get_score_by_challenge(&mut open_db_connection(), &challenge) {
+        Ok(challenge) => challenge,
         Err(rusqlite::Error::QueryReturnedNoRows) => {
-            return Err::<(), _>((StatusCode::NOT_FOUND, "Game with specified ID not found"))
-                .into_response()
+            return Err::<(), _>((StatusCode::NOT_FOUND, "Challenge not found")).into_response()
         }
         Err(_) => {
             return Err::<(), _>((
                 StatusCode::INTERNAL_SERVER_ERROR,
-                "Error fetching game from database",
+                "Error fetching challenge from database",
             ))
             .into_response()
         }
     };
-    let Query(time) =
-        time.unwrap_or_else(|| Query(GameTime::from_game_size(game_data.size()).unwrap()));
+    let game = match fetch_game(&challenge.game_id.to_string()) {
+        Ok(game) => game,
+        Err(e) => return Err::<(), _>(e).into_response(),
+    };
     let user = get_uid_from_cookie(jar);
+    let time = challenge.time as u64;
     ws.on_upgrade(move |socket| {
-        handle_socket_game(socket, addr, Game::from(id, game_data), time.time, user)
+        handle_socket_game(socket, addr, game, time, user, options, Some(challenge))
     })
     .into_response()
 }
 
+/// Joins the room playing the game with `id`, opening one if nobody is playing it yet.
+///
+/// Everyone in a room plays the same board against the same clock, which starts when the room is
+/// opened; `time` only applies to a new room.
+#[allow(clippy::too_many_arguments)]
+pub async fn get_room_game(
+    Path(id): Path<String>,
+    State(rooms): State<Rooms>,
+    time: Option<Query<GameTime>>,
+    Query(options): Query<SocketOptions>,
+    Query(room_options): Query<RoomOptions>,
+    jar: SignedCookieJar,
+    ws: WebSocketUpgrade,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+) -> impl IntoResponse {
+    let room = match Room::get(&rooms, &id).filter(|r| r.code.is_none()) {
+        Some(room) => room,
+        None => {
+            let game = match fetch_game(&id) {
+                Ok(game) => game,
+                Err(e) => return Err::<(), _>(e).into_response(),
+            };
+            let Query(time) =
+                time.unwrap_or_else(|| Query(GameTime::from_shape(&game.data.shape()).unwrap()));
+            match Room::open(&rooms, game, time.time, room_options.mode) {
+                Some(room) => room,
+                None => {
+                    return Err::<(), _>((StatusCode::BAD_REQUEST, "Invalid game time"))
+                        .into_response()
+                }
+            }
+        }
+    };
+    let user = get_uid_from_cookie(jar);
+    if user.is_some_and(|user| room.has_user(user)) {
+        return Err::<(), _>((StatusCode::CONFLICT, "You are already playing in this room"))
+            .into_response();
+    }
+    let name = player_name(user, room_options.name);
+    ws.on_upgrade(move |socket| handle_socket_room(socket, addr, room, name, user, options))
+        .into_response()
+}
+
+/// Opens a lobby for a new game with `size` rows and the given options, and joins it as its host.
+///
+/// Nobody sees the board until the host starts the game, which starts it for everyone in the
+/// lobby at once.
+#[allow(clippy::too_many_arguments)]
+pub async fn get_new_lobby(
+    Path(size): Path<usize>,
+    State(rooms): State<Rooms>,
+    time: Option<Query<GameTime>>,
+    Query(game_options): Query<NewGameOptions>,
+    Query(options): Query<SocketOptions>,
+    Query(room_options): Query<RoomOptions>,
+    jar: SignedCookieJar,
+    ws: WebSocketUpgrade,
+) -> impl IntoResponse {
+    let (config, default_time) = match game_options.config(size) {
+        Ok(config) => config,
+        Err(e) => return Err::<(), _>(e).into_response(),
+    };
+    let Query(time) = time.unwrap_or(Query(default_time));
+    let user = get_uid_from_cookie(jar);
+    let name = player_name(user, room_options.name);
+    let game = Game::generate(config, game_options.scoring).await;
+    let Some(room) = Room::open_lobby(&rooms, game, time.time, room_options.mode) else {
+        return Err::<(), _>((StatusCode::BAD_REQUEST, "Invalid game time")).into_response();
+    };
+    ws.on_upgrade(move |socket| handle_socket_lobby(socket, room, rooms, name, user, options))
+        .into_response()
+}
+
+/// Joins the lobby with the join `code`, to wait there until its host starts the game.
+pub async fn get_lobby(
+    Path(code): Path<String>,
+    State(rooms): State<Rooms>,
+    Query(options): Query<SocketOptions>,
+    Query(room_options): Query<RoomOptions>,
+    jar: SignedCookieJar,
+    ws: WebSocketUpgrade,
+) -> impl IntoResponse {
+    let Some(room) = Room::get(&rooms, &code.trim().to_uppercase()).filter(|r| r.code.is_some())
+    else {
+        return Err::<(), _>((StatusCode::NOT_FOUND, "No lobby found with this code"))
+            .into_response();
+    };
+    if room.is_started() {
+        return Err::<(), _>((StatusCode::CONFLICT, "This game has already started"))
+            .into_response();
+    }
+    let user = get_uid_from_cookie(jar);
+    if user.is_some_and(|user| room.has_user(user)) {
+        return Err::<(), _>((StatusCode::CONFLICT, "You are already playing in this room"))
+            .into_response();
+    }
+    let name = player_name(user, room_options.name);
+    ws.on_upgrade(move |socket| handle_socket_lobby(socket, room, rooms, name, user, options))
+        .into_response()
+}
+
+/// Returns the name to show other players for `user`, which is their username if they're logged
+/// in, or else the name they asked for.
+fn player_name(user: Option<UserID>, name: Option<String>) -> String {
+    match user {
+        Some(user) => get_username(&mut open_db_connection(), user).ok(),
+        None => name
+            .map(|name| name.trim().chars().take(MAX_NAME_LENGTH).collect())
+            .filter(|name: &String| !name.is_empty()),
+    }
+    .unwrap_or_else(|| "Guest".to_string())
+}
+
 pub async fn get_daily_game(
+    Query(options): Query<SocketOptions>,
     jar: SignedCookieJar,
     ws: WebSocketUpgrade,
     ConnectInfo(addr): ConnectInfo<SocketAddr>,
@@ -115,10 +476,22 @@ pub async fn get_daily_game(
     let user = get_uid_from_cookie(jar);
     let game = DailyGame::get().0;
     ws.on_upgrade(move |socket| {
-        handle_socket_game(socket, addr, game, GameTime::default().time, user)
+        handle_socket_game(
+            socket,
+            addr,
+            game,
+            GameTime::default().time,
+            user,
+            options,
+            None,
+        )
     })
 }
 
+pub async fn get_dictionaries() -> impl IntoResponse {
+    Json(get_dictionary_ids())
+}
+
 pub async fn get_score(jar: SignedCookieJar, Path(game_id): Path<String>) -> impl IntoResponse {
     let Some(uid) = get_uid_from_cookie(jar) else {
         return Err((StatusCode::UNAUTHORIZED, "You are not currently logged in"));
@@ -171,3 +544,113 @@ pub async fn get_stats(Json(payload): Json<serde_json::Value>) -> impl IntoRespo
     };
     Ok((StatusCode::OK, Json(res)))
 }
+
+#[cfg(test)]
+mod test {
+    use axum::http::Uri;
+
+    use super::*;
+
+    /// Parses the new game options in `query`, as they would be given to `/game/:size`.
+    fn options(query: &str) -> Result<NewGameOptions, String> {
+        let uri: Uri = format!("/game/4?{query}").parse().unwrap();
+        Query::try_from_uri(&uri)
+            .map(|Query(options)| options)
+            .map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn game_time_is_bounded() {
+        let time = |query: &str| {
+            let uri: Uri = format!("/game/4?{query}").parse().unwrap();
+            Query::<GameTime>::try_from_uri(&uri).map(|Query(time)| time.time)
+        };
+        assert_eq!(time("time=90").unwrap(), 90);
+        assert_eq!(
+            time(&format!("time={MAX_GAME_TIME}")).unwrap(),
+            MAX_GAME_TIME
+        );
+        assert!(time(&format!("time={}", MAX_GAME_TIME + 1)).is_err());
+        assert!(time(&format!("time={}", u64::MAX)).is_err());
+    }
+
+    #[test]
+    fn letter_weights_option() {
+        let (config, _) = options("weights=e:3,qu:1").unwrap().config(4).unwrap();
+        assert_eq!(
+            config.distribution,
+            LetterDistribution::Weights("e:3,qu:1".parse().unwrap())
+        );
+        assert!(options("weights=e:0,t:0").is_err());
+        assert!(options("weights=e3").is_err());
+        assert!(options("weights=a:18446744073709551615,b:1").is_err());
+    }
+
+    #[test]
+    fn min_word_length_fits_the_board() {
+        let (config, _) = options("min_word_length=6").unwrap().config(3).unwrap();
+        assert_eq!(config.rules.min_word_length, 6);
+        // a 3x3 board hides words of at most 8 letters, and a 7x7 one of at most 20
+        assert!(options("min_word_length=9").unwrap().config(3).is_err());
+        assert!(options("min_word_length=25").unwrap().config(7).is_err());
+        assert!(options("min_word_length=0").unwrap().config(4).is_err());
+    }
+
+    #[test]
+    fn wildcard_density_is_capped() {
+        let (config, _) = options("wildcard=0.2").unwrap().config(7).unwrap();
+        assert_eq!(config.modifiers.wildcard, 0.2);
+        assert!(options("wildcard=0.5").unwrap().config(7).is_err());
+        assert!(options("wildcard=1.0").unwrap().config(7).is_err());
+    }
+
+    #[test]
+    fn holes_option() {
+        let (config, _) = options("holes=0,0;3,3").unwrap().config(4).unwrap();
+        assert_eq!(config.shape.mask, Mask::Holes(vec![(0, 0), (3, 3)]));
+        assert_eq!(config.shape.len(), 14);
+
+        assert!(options("holes=0").unwrap().config(4).is_err());
+        // more than half the board
+        let holes = "0,0;0,1;0,2;0,3;1,0;1,1;1,2;1,3;2,0";
+        assert!(options(&format!("holes={holes}"))
+            .unwrap()
+            .config(4)
+            .is_err());
+        // holes in every other column split the board into columns too short to hide a word in
+        let holes = "0,1;0,3;0,5;1,1;1,3;1,5;2,1;2,3;2,5";
+        assert!(options(&format!("cols=7&holes={holes}"))
+            .unwrap()
+            .config(3)
+            .is_err());
+    }
+
+    #[test]
+    fn board_constraint_options() {
+        let (config, _) = options("min_words=30&max_words=90&min_long_words=2&max_repeated_tile=3")
+            .unwrap()
+            .config(4)
+            .unwrap();
+        assert_eq!(
+            config.constraints,
+            BoardConstraints {
+                min_words: 30,
+                max_words: Some(90),
+                min_long_words: 2,
+                max_repeated_tile: Some(3),
+                ..Default::default()
+            }
+        );
+        let (config, _) = options("max_attempts=20").unwrap().config(4).unwrap();
+        assert_eq!(config.constraints.max_attempts, 20);
+
+        for query in [
+            "min_words=50&max_words=10",
+            "max_repeated_tile=0",
+            "max_attempts=0",
+            "max_attempts=1000",
+        ] {
+            assert!(options(query).unwrap().config(4).is_err(), "{query}");
+        }
+    }
+}
@@ -43,18 +43,13 @@ pub async fn create_new_user(
     let id = match add_user(conn, &data.username, &data.email, &data.password) {
         Ok(id) => id,
         Err(e) => match e.downcast() {
-            Ok(rusqlite::Error::SqliteFailure(e, _)) => {
-                if e.code == rusqlite::ErrorCode::ConstraintViolation {
-                    return Err((
-                        StatusCode::CONFLICT,
-                        "A user with this username or email already exists",
-                    ));
-                } else {
-                    return Err((
-                        StatusCode::INTERNAL_SERVER_ERROR,
-                        "Error adding new user to database",
-                    ));
-                }
+            Ok(rusqlite::Error::SqliteFailure(e, _))
+                if e.code == rusqlite::ErrorCode::ConstraintViolation =>
+            {
+                return Err((
+                    StatusCode::CONFLICT,
+                    "A user with this username or email already exists",
+                ));
             }
             _ => {
                 return Err((
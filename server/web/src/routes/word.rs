@@ -0,0 +1,18 @@
+use axum::{extract::Path, http::StatusCode, response::IntoResponse, Json};
+use serde::Serialize;
+use wordtwist::words::Definitions;
+
+#[derive(Serialize)]
+struct WordDTO<'a> {
+    word: String,
+    definitions: &'a [String],
+}
+
+pub async fn get_word(Path(word): Path<String>) -> impl IntoResponse {
+    let word = word.to_lowercase();
+    let definitions = Definitions::global().get(&word);
+    if definitions.is_empty() {
+        return Err((StatusCode::NOT_FOUND, "No definitions found for word"));
+    }
+    Ok((StatusCode::OK, Json(WordDTO { word, definitions })))
+}
@@ -1,15 +1,25 @@
-use std::{borrow::Cow, net::SocketAddr, time::Duration};
+use std::{borrow::Cow, collections::BTreeMap, net::SocketAddr, sync::Arc, time::Duration};
 
 use axum::extract::ws::{close_code::NORMAL, Message, WebSocket};
-use serde::Serialize;
-use serde_json;
-use tokio::time;
+use serde::{Deserialize, Serialize};
+use tokio::{
+    sync::broadcast::{self, error::RecvError},
+    time,
+};
 use uuid::Uuid;
-use wordtwist::game::GameResults;
+use wordtwist::{
+    game::{FoundWord, GameResults, Modifiers, PathError, Shape, WordComparison, LONG_WORD_LENGTH},
+    words::{Definitions, Grid, Topology},
+};
 
 use crate::{
-    db::{game::add_game_score, open_db_connection, user::UserID},
+    db::{
+        game::{add_game_score, add_team_game_score, ScoreRecord},
+        open_db_connection,
+        user::UserID,
+    },
     game::Game,
+    room::{Guess, Room, RoomEvent, RoomMode, Rooms, Standing, TeamStanding},
 };
 
 #[derive(Serialize)]
@@ -19,20 +29,136 @@ enum SocketResponse<'a> {
     GuessResponse {
         word: &'a str,
         valid: bool,
+        /// Points awarded for the word, or 0 if it was rejected.
+        score: usize,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        reason: Option<GuessError>,
+        /// The player who already claimed the word, in race mode.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        claimed_by: Option<&'a str>,
     },
     GameOver {
-        results: GameResults,
+        results: &'a GameResults,
         msg: Option<&'a str>,
+        /// A short definition of each long missed word that has one, if they were asked for.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        definitions: Option<BTreeMap<&'a str, &'static str>>,
+        /// Every player's result, best first, in games played together in a room.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        standings: Option<Vec<Standing>>,
+        /// Every team's result, best first, in team mode.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        teams: Option<Vec<TeamStanding>>,
+        /// The token others can play against the saved score with as a challenge.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        challenge_id: Option<String>,
+        /// How the words found compare to the challenger's, in challenge games.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        head_to_head: Option<HeadToHead<'a>>,
     },
     Setup {
         time: u64,
         game: GameSetupDTO<'a>,
     },
+    /// The waiting room of a lobby, sent on joining it.
+    Lobby {
+        /// The code others can join the lobby with.
+        code: &'a str,
+        /// The name this player was given, which is numbered if someone else already had it.
+        name: &'a str,
+        players: Vec<String>,
+        host: Option<&'a str>,
+        mode: RoomMode,
+    },
+    /// Someone else joined the room, on `team` in team mode.
+    PlayerJoined {
+        name: &'a str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        team: Option<usize>,
+    },
+    /// The team this player is on in team mode, sent when the game starts.
+    Team {
+        team: usize,
+        teammates: Vec<String>,
+    },
+    /// A teammate found `word` for the team in team mode, worth `score` points.
+    TeammateFound {
+        name: &'a str,
+        word: &'a str,
+        score: usize,
+    },
+    /// Someone else left the lobby, leaving `host` as the player who can start the game.
+    PlayerLeft {
+        name: &'a str,
+        host: Option<&'a str>,
+    },
+    /// Someone else claimed `word` in race mode, so it can no longer be found.
+    WordClaimed {
+        name: &'a str,
+        word: &'a str,
+    },
+    /// Someone else in the room found a word, bringing them to `words` words worth `score` points.
+    PlayerFound {
+        name: &'a str,
+        words: usize,
+        score: usize,
+    },
+}
+
+/// Why a guess was rejected, sent back to the client in a `GuessResponse`.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+enum GuessError {
+    /// The message couldn't be parsed as a guess.
+    Malformed,
+    /// The word was already found earlier in this game, by this player or, in team mode, their
+    /// team.
+    AlreadyFound,
+    /// The word was already claimed by someone else, in race mode.
+    AlreadyClaimed,
+    #[serde(untagged)]
+    Path(PathError),
+}
+
+/// Per-connection protocol options, provided as query parameters when opening the socket.
+#[derive(Deserialize, Default, Clone, Copy)]
+pub struct SocketOptions {
+    /// When set, guesses are JSON arrays of `[y, x]` squares rather than plain words, and are
+    /// checked against the grid before being accepted.
+    #[serde(default)]
+    paths: bool,
+    /// When set, the game over message includes definitions of the long words that were missed.
+    #[serde(default)]
+    definitions: bool,
+}
+
+/// A path guess, optionally naming the word it spells, for paths through blank squares.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PathGuess {
+    Path(Vec<(usize, usize)>),
+    Spelling {
+        path: Vec<(usize, usize)>,
+        word: String,
+    },
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct HeadToHead<'a> {
+    opponent: &'a str,
+    opponent_score: usize,
+    #[serde(flatten)]
+    words: WordComparison,
 }
 
 #[derive(Serialize)]
 struct GameSetupDTO<'a> {
-    grid: &'a Vec<Vec<char>>,
+    grid: &'a Grid,
+    shape: Shape,
+    topology: Topology,
+    /// The special square on each square of the grid, or empty if there are none.
+    modifiers: &'a Modifiers,
     id: &'a str,
 }
 
@@ -42,7 +168,79 @@ pub async fn handle_socket_game(
     game: Game,
     time: u64,
     user: Option<UserID>,
+    options: SocketOptions,
+    challenge: Option<ScoreRecord>,
 ) {
+    send_setup(&mut socket, &game, time).await;
+
+    tokio::spawn(async move {
+        let mut submitted_words = Vec::with_capacity(game.data.valid_words().len());
+
+        let timeout = time::sleep(Duration::from_secs(time));
+        tokio::pin!(timeout);
+
+        loop {
+            tokio::select! {
+                _ = &mut timeout => {
+                    let results = game.data.score(submitted_words);
+                    let (challenge_id, msg) = match user {
+                        Some(user) => save_score(&game.id, user, &results, time, None),
+                        None => (None, None),
+                    };
+                    let head_to_head = challenge.as_ref().map(|challenge| HeadToHead {
+                        opponent: &challenge.username,
+                        opponent_score: challenge.score,
+                        words: results.compare(&challenge.words),
+                    });
+                    let response = SocketResponse::GameOver {
+                        results: &results,
+                        msg,
+                        definitions: options.definitions.then(|| missed_word_definitions(&results)),
+                        standings: None,
+                        teams: None,
+                        challenge_id,
+                        head_to_head,
+                    };
+                    send_game_over(socket, &response).await;
+                    break;
+                }
+                s = socket.recv() => {
+                    let Some(Ok(Message::Text(guess))) = s else {
+                        break;
+                    };
+                    let rejected_word;
+                    let response = match check_guess(&game, options, &guess, &submitted_words) {
+                        Ok(found) => {
+                            submitted_words.push(found);
+                            let found = submitted_words.last().unwrap();
+                            SocketResponse::GuessResponse {
+                                word: &found.word,
+                                valid: true,
+                                score: game.data.score_word(&found.word, found.path.as_deref()),
+                                reason: None,
+                                claimed_by: None,
+                            }
+                        }
+                        Err((word, reason)) => {
+                            rejected_word = word;
+                            SocketResponse::GuessResponse {
+                                word: &rejected_word,
+                                valid: false,
+                                score: 0,
+                                reason: Some(reason),
+                                claimed_by: None,
+                            }
+                        }
+                    };
+                    let _ = socket.send(Message::Text(serde_json::to_string(&response).unwrap())).await;
+                }
+            }
+        }
+    });
+}
+
+/// Sends the board and time limit of `game` to the client.
+async fn send_setup(socket: &mut WebSocket, game: &Game, time: u64) {
     // ignoring potential errors here, since if the client fails to establish the socket
     // there isn't anything we can do here anyway
     let _ = socket
@@ -50,6 +248,9 @@ pub async fn handle_socket_game(
             serde_json::to_string(&SocketResponse::Setup {
                 game: GameSetupDTO {
                     grid: game.data.grid(),
+                    shape: game.data.shape(),
+                    topology: game.data.topology(),
+                    modifiers: game.data.modifiers(),
                     id: &game.id,
                 },
                 time,
@@ -58,84 +259,322 @@ pub async fn handle_socket_game(
         ))
         .await
         .is_ok();
+}
 
-    tokio::spawn(async move {
-        let mut submitted_words = Vec::with_capacity(game.data.valid_words().len());
+/// Plays `room`'s game over `socket` as a new player called `name`, alongside everyone else in
+/// the room, until the room's timer runs out.
+pub async fn handle_socket_room(
+    mut socket: WebSocket,
+    _: SocketAddr,
+    room: Arc<Room>,
+    name: String,
+    user: Option<UserID>,
+    options: SocketOptions,
+) {
+    let (me, events) = room.join(name, user);
+    send_setup(&mut socket, &room.game, room.remaining_time()).await;
+    send_team(&mut socket, &room, me).await;
+    tokio::spawn(play_in_room(socket, room, me, events, user, options));
+}
 
-        let timeout = time::sleep(Duration::from_secs(time));
-        tokio::pin!(timeout);
+/// Waits in the lobby `room` over `socket` as a new player called `name` until the host starts
+/// the game, then plays it alongside everyone else in the lobby.
+///
+/// The host starts the game by sending `start`; anything else sent while waiting is ignored.
+pub async fn handle_socket_lobby(
+    mut socket: WebSocket,
+    room: Arc<Room>,
+    rooms: Rooms,
+    name: String,
+    user: Option<UserID>,
+    options: SocketOptions,
+) {
+    let (me, mut events) = room.join(name, user);
+    let host = room.host().map(|host| room.name(host));
+    let _ = socket
+        .send(Message::Text(
+            serde_json::to_string(&SocketResponse::Lobby {
+                code: room.code.as_deref().unwrap_or_default(),
+                name: &room.name(me),
+                players: room.players(),
+                host: host.as_deref(),
+                mode: room.mode,
+            })
+            .unwrap(),
+        ))
+        .await;
 
-        loop {
+    tokio::spawn(async move {
+        // the game may have started between checking the lobby and joining it
+        while !room.is_started() {
             tokio::select! {
-                _ = &mut timeout => {
-                    handle_end_game(socket, game, user, time, submitted_words).await;
-                    break;
+                event = events.recv() => {
+                    let response = match event {
+                        Ok(RoomEvent::Joined { player, name, team }) if player != me => {
+                            serde_json::to_string(&SocketResponse::PlayerJoined {
+                                name: &name,
+                                team,
+                            })
+                        }
+                        Ok(RoomEvent::Left { name, .. }) => {
+                            let host = room.host().map(|host| room.name(host));
+                            serde_json::to_string(&SocketResponse::PlayerLeft {
+                                name: &name,
+                                host: host.as_deref(),
+                            })
+                        }
+                        Ok(RoomEvent::Started) => break,
+                        Ok(RoomEvent::Over) | Err(RecvError::Closed) => {
+                            close_socket(socket, "lobby closed").await;
+                            return;
+                        }
+                        Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                    };
+                    let _ = socket.send(Message::Text(response.unwrap())).await;
                 }
                 s = socket.recv() => {
-                    if let Some(Ok(Message::Text(word))) = s {
-                        if game.data.valid_words().contains(&word) && !submitted_words.contains(&word) {
-                            submitted_words.push(word);
-                            let _ = socket.send(Message::Text(
-                                serde_json::to_string(&SocketResponse::GuessResponse {
-                                     word: submitted_words.last().unwrap(), valid: true
-                                    }).unwrap()
-                            )).await;
-                        } else {
-                            let _ = socket.send(Message::Text(
-                                serde_json::to_string(&SocketResponse::GuessResponse {
-                                word: &word, valid: false
-                                }).unwrap()
-                            )).await;
+                    match s {
+                        Some(Ok(Message::Text(msg))) => {
+                            if msg == "start" && room.host() == Some(me) {
+                                room.start(&rooms);
+                            }
+                        }
+                        Some(Ok(_)) => {}
+                        _ => {
+                            room.leave(me);
+                            return;
                         }
-                    } else {
-                        break;
                     }
                 }
             }
         }
+        send_setup(&mut socket, &room.game, room.remaining_time()).await;
+        send_team(&mut socket, &room, me).await;
+        play_in_room(socket, room, me, events, user, options).await;
     });
 }
 
-async fn handle_end_game(
+/// Tells `player` which team they're on and who their teammates are, in team mode.
+async fn send_team(socket: &mut WebSocket, room: &Room, player: usize) {
+    let Some(team) = room.team(player) else {
+        return;
+    };
+    let teammates = room.teammates(player);
+    let _ = socket
+        .send(Message::Text(
+            serde_json::to_string(&SocketResponse::Team { team, teammates }).unwrap(),
+        ))
+        .await;
+}
+
+/// Plays `room`'s game over `socket` as `player`, passing on what the other players do, until
+/// the room's timer runs out.
+async fn play_in_room(
     mut socket: WebSocket,
-    game: Game,
+    room: Arc<Room>,
+    me: usize,
+    mut events: broadcast::Receiver<RoomEvent>,
     user: Option<UserID>,
-    time: u64,
-    submitted_words: Vec<String>,
+    options: SocketOptions,
 ) {
-    let game_id = Uuid::parse_str(&game.id).unwrap();
-    let results = game.data.score(submitted_words);
-    let mut err = None;
-    if user.is_some() {
-        let conn = &mut open_db_connection();
-        err = match add_game_score(conn, game_id, user.unwrap(), results.score, time as usize) {
-            Err(rusqlite::Error::SqliteFailure(e, _)) => {
-                if e.code == rusqlite::ErrorCode::ConstraintViolation {
-                    // eprintln!("user {user:?} has already been scored for game {game_id:?}")
-                    Some("You have already sumbitted a score for this game")
-                } else {
-                    Some("Error adding game score to database")
-                }
+    loop {
+        tokio::select! {
+            event = events.recv() => {
+                let response = match event {
+                    Ok(RoomEvent::Joined { player, name, team }) if player != me => {
+                        serde_json::to_string(&SocketResponse::PlayerJoined { name: &name, team })
+                    }
+                    Ok(RoomEvent::TeamFound { team, player, word, score })
+                        if player != me && room.team(me) == Some(team) =>
+                    {
+                        serde_json::to_string(&SocketResponse::TeammateFound {
+                            name: &room.name(player),
+                            word: &word,
+                            score,
+                        })
+                    }
+                    Ok(RoomEvent::Found { player, words, score }) if player != me => {
+                        serde_json::to_string(&SocketResponse::PlayerFound {
+                            name: &room.name(player),
+                            words,
+                            score,
+                        })
+                    }
+                    Ok(RoomEvent::Claimed { player, word }) if player != me => {
+                        serde_json::to_string(&SocketResponse::WordClaimed {
+                            name: &room.name(player),
+                            word: &word,
+                        })
+                    }
+                    Ok(RoomEvent::Over) | Err(RecvError::Closed) => {
+                        let results = room.game.data.clone().score(room.words(me));
+                        let (challenge_id, msg) = match (user, room.team(me)) {
+                            // team members are saved with the words they found themselves, alongside
+                            // their team's score
+                            (Some(user), Some(team)) => {
+                                let own = room.game.data.clone().score(room.own_words(me));
+                                save_score(&room.game.id, user, &own, room.time, Some((team, results.score)))
+                            }
+                            (Some(user), None) => {
+                                save_score(&room.game.id, user, &results, room.time, None)
+                            }
+                            (None, _) => (None, None),
+                        };
+                        let response = SocketResponse::GameOver {
+                            results: &results,
+                            msg,
+                            definitions: options.definitions.then(|| missed_word_definitions(&results)),
+                            standings: Some(room.standings()),
+                            teams: room.team_standings(),
+                            challenge_id,
+                            head_to_head: None,
+                        };
+                        send_game_over(socket, &response).await;
+                        break;
+                    }
+                    // other players' progress is only informational, so missing some is fine
+                    Ok(_) | Err(RecvError::Lagged(_)) => continue,
+                };
+                let _ = socket.send(Message::Text(response.unwrap())).await;
+            }
+            s = socket.recv() => {
+                let Some(Ok(Message::Text(guess))) = s else {
+                    break;
+                };
+                let guessed = room.guess(me, |submitted_words| {
+                    check_guess(&room.game, options, &guess, submitted_words)
+                });
+                let (word, score, reason, claimed_by) = match guessed {
+                    // the timer ran out, and the game over message is on its way
+                    None => continue,
+                    Some(Guess::Accepted { word, score }) => (word, score, None, None),
+                    Some(Guess::Rejected((word, reason))) => (word, 0, Some(reason), None),
+                    Some(Guess::Claimed { word, by }) => {
+                        (word, 0, Some(GuessError::AlreadyClaimed), Some(by))
+                    }
+                };
+                let response = SocketResponse::GuessResponse {
+                    word: &word,
+                    valid: reason.is_none(),
+                    score,
+                    reason,
+                    claimed_by: claimed_by.as_deref(),
+                };
+                let _ = socket.send(Message::Text(serde_json::to_string(&response).unwrap())).await;
             }
-            Err(_) => {
-                // eprintln!("failed to add game {game_id:?} to database (for user {user:?}")
-                Some("Error adding game score to database")
+        }
+    }
+}
+
+/// Checks a guess received over the socket, returning the guessed word, along with the path it was
+/// traced along, if it should be accepted.
+///
+/// On rejection, returns the word that was guessed (empty if a path couldn't be spelled) along
+/// with the reason it was rejected.
+fn check_guess(
+    game: &Game,
+    options: SocketOptions,
+    guess: &str,
+    submitted_words: &[FoundWord],
+) -> Result<FoundWord, (String, GuessError)> {
+    let found = if options.paths {
+        let Ok(guess) = serde_json::from_str::<PathGuess>(guess) else {
+            return Err((String::new(), GuessError::Malformed));
+        };
+        let (word, path) = match guess {
+            PathGuess::Path(path) => (game.data.validate_path(&path), path),
+            PathGuess::Spelling { path, word } => {
+                (game.data.validate_path_spelling(&path, &word), path)
             }
-            Ok(_) => Some("Score saved!"),
+        };
+        FoundWord {
+            word: word.map_err(|e| (String::new(), GuessError::Path(e)))?,
+            path: Some(path),
+        }
+    } else {
+        if !game.data.validate(guess) {
+            return Err((guess.to_string(), GuessError::Path(PathError::NotAWord)));
         }
+        FoundWord::from(guess.to_string())
+    };
+    if submitted_words.iter().any(|w| w.word == found.word) {
+        return Err((found.word, GuessError::AlreadyFound));
     }
+    Ok(found)
+}
+
+/// Saves `results` as `user`'s score for the game with `game_id`, along with their team and its
+/// score in team mode, returning the challenge token of the saved score and a message saying
+/// whether it was saved.
+fn save_score(
+    game_id: &str,
+    user: UserID,
+    results: &GameResults,
+    time: u64,
+    team: Option<(usize, usize)>,
+) -> (Option<String>, Option<&'static str>) {
+    let game_id = Uuid::parse_str(game_id).unwrap();
+    let conn = &mut open_db_connection();
+    let words = &results.found_words;
+    let saved = match team {
+        Some((team, team_score)) => add_team_game_score(
+            conn,
+            game_id,
+            user,
+            results.score,
+            time as usize,
+            words,
+            team,
+            team_score,
+        ),
+        None => add_game_score(conn, game_id, user, results.score, time as usize, words),
+    };
+    let msg = match &saved {
+        Err(rusqlite::Error::SqliteFailure(e, _)) => {
+            if e.code == rusqlite::ErrorCode::ConstraintViolation {
+                // eprintln!("user {user:?} has already been scored for game {game_id:?}")
+                "You have already sumbitted a score for this game"
+            } else {
+                "Error adding game score to database"
+            }
+        }
+        Err(_) => {
+            // eprintln!("failed to add game {game_id:?} to database (for user {user:?}")
+            "Error adding game score to database"
+        }
+        Ok(_) => "Score saved!",
+    };
+    (saved.ok(), Some(msg))
+}
+
+/// Sends the final `response` of a game to the client, then closes the socket.
+async fn send_game_over(mut socket: WebSocket, response: &SocketResponse<'_>) {
     let _ = socket
-        .send(Message::Text(
-            serde_json::to_string(&SocketResponse::GameOver { results, msg: err }).unwrap(),
-        ))
+        .send(Message::Text(serde_json::to_string(response).unwrap()))
         .await
         .is_err();
+    close_socket(socket, "game over").await;
+}
+
+async fn close_socket(mut socket: WebSocket, reason: &'static str) {
     let _ = socket
         .send(Message::Close(Some({
             axum::extract::ws::CloseFrame {
                 code: NORMAL,
-                reason: Cow::from("game over"),
+                reason: Cow::from(reason),
             }
         })))
         .await;
 }
+
+/// Returns the first definition of each missed word of at least [`LONG_WORD_LENGTH`] letters that
+/// has one.
+fn missed_word_definitions(results: &GameResults) -> BTreeMap<&str, &'static str> {
+    let definitions = Definitions::global();
+    results
+        .missed_words
+        .iter()
+        .filter(|w| w.chars().count() >= LONG_WORD_LENGTH)
+        .filter_map(|w| Some((w.as_str(), definitions.first(w)?)))
+        .collect()
+}
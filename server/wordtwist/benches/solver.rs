@@ -0,0 +1,42 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use wordtwist::{
+    game::{Game, GameRules},
+    words::{generate_wordlist_by_word_search, generate_wordlist_from_game, Dictionary, Topology},
+};
+
+/// Compares the trie solver against the original per-word search across every game size.
+fn solvers(c: &mut Criterion) {
+    let dictionary = Dictionary::embedded();
+    let mut group = c.benchmark_group("solve");
+    group.sample_size(10);
+
+    for size in 3..=7 {
+        let game = Game::from_seed(size, 0);
+        let grid = game.grid();
+
+        group.bench_with_input(BenchmarkId::new("trie", size), grid, |b, grid| {
+            b.iter(|| {
+                generate_wordlist_from_game(
+                    grid,
+                    Topology::Bounded,
+                    &GameRules::default(),
+                    dictionary,
+                )
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("word_search", size), grid, |b, grid| {
+            b.iter(|| {
+                generate_wordlist_by_word_search(
+                    grid,
+                    Topology::Bounded,
+                    &GameRules::default(),
+                    dictionary,
+                )
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, solvers);
+criterion_main!(benches);
@@ -1,20 +1,94 @@
+use std::{cmp::Reverse, collections::HashMap, error::Error, fmt};
+
 use rand::{
     seq::{IteratorRandom, SliceRandom},
-    thread_rng,
+    thread_rng, Rng, SeedableRng,
 };
+use rand_chacha::ChaCha8Rng;
 use serde::{Deserialize, Serialize};
 
-use crate::words::{generate_wordlist_from_game, get_random_letter, get_random_n_length_word};
+use crate::words::{
+    get_random_word_with_length_in, solve_game, tiles_spell, Blocklist, Dictionary, Grid,
+    LetterDistribution, Path, Solution, Topology, DEFAULT_DICTIONARY, MAX_BLANKS_PER_WORD,
+    WILDCARD,
+};
+
+mod difficulty;
+mod modifiers;
+mod quality;
+mod rules;
+mod scoring;
+mod shape;
+
+pub use difficulty::{difficulty_score, Difficulty};
+pub use modifiers::{Modifier, ModifierDensity, Modifiers};
+pub use quality::{BoardConstraints, BoardStats, LONG_WORD_LENGTH};
+pub use rules::GameRules;
+pub use scoring::{ClassicBoggle, Exponential, LetterValue, Scoring, ScoringRule, TargetWordBonus};
+pub use shape::{Mask, Shape};
+
+/// How many tiles are drawn when looking for a replacement for a tile rerolled by a repair.
+const REPAIR_CANDIDATES: usize = 8;
+
+/// The reasons a traced path of squares can be rejected by [`Game::validate_path`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PathError {
+    /// The path contains no squares.
+    Empty,
+    /// A square in the path lies outside the grid.
+    OutOfBounds,
+    /// Two consecutive squares in the path aren't adjacent.
+    NotAdjacent,
+    /// The path visits the same square more than once.
+    ReusedSquare,
+    /// The path is well formed, but doesn't spell a valid word.
+    NotAWord,
+    /// The path crosses blank squares, and spells more than one valid word.
+    Ambiguous,
+}
+
+impl fmt::Display for PathError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PathError::Empty => "path is empty",
+            PathError::OutOfBounds => "path leaves the grid",
+            PathError::NotAdjacent => "path jumps between squares that aren't adjacent",
+            PathError::ReusedSquare => "path uses the same square more than once",
+            PathError::NotAWord => "path doesn't spell a valid word",
+            PathError::Ambiguous => "path spells more than one valid word",
+        })
+    }
+}
+
+impl Error for PathError {}
+
+/// The error returned when a target word can't be laid out along a path of squares of the board,
+/// such as when holes split the board into regions too small for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LayoutError;
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("target word doesn't fit in the board")
+    }
+}
+
+impl Error for LayoutError {}
 
-enum GameDirections {
-    Up,
-    Down,
-    Left,
-    Right,
-    UpLeft,
-    UpRight,
-    DownLeft,
-    DownRight,
+/// A word a player found, along with the squares they traced it through, if they traced it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FoundWord {
+    pub word: String,
+    /// The path the word was traced along, which must have been checked with
+    /// [`Game::validate_path`].
+    pub path: Option<Path>,
+}
+
+impl From<String> for FoundWord {
+    fn from(word: String) -> Self {
+        FoundWord { word, path: None }
+    }
 }
 
 #[derive(Serialize)]
@@ -22,13 +96,147 @@ enum GameDirections {
 pub struct GameResults {
     pub found_words: Vec<String>,
     pub missed_words: Vec<String>,
+    /// A path spelling each of the `missed_words`.
+    pub missed_paths: Solution,
     pub score: usize,
+    /// The word hidden in the grid when it was generated, revealed once the game is over.
+    pub target_word: Option<String>,
+    pub target_path: Option<Path>,
+    /// Whether the hidden target word was among the `found_words`.
+    pub found_target_word: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+/// How the words found in a game compare with the words another player found in the same game.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WordComparison {
+    /// Words both players found.
+    pub shared: Vec<String>,
+    /// Words only found in these results.
+    pub only_yours: Vec<String>,
+    /// Words only the other player found.
+    pub only_theirs: Vec<String>,
+}
+
+impl GameResults {
+    /// Compares the words found in these results with `other_words`, found by another player in
+    /// the same game. Words are listed longest first.
+    pub fn compare(&self, other_words: &[String]) -> WordComparison {
+        let (shared, only_yours) = self
+            .found_words
+            .iter()
+            .cloned()
+            .partition(|w| other_words.contains(w));
+        let mut only_theirs: Vec<String> = other_words
+            .iter()
+            .filter(|w| !self.found_words.contains(w))
+            .cloned()
+            .collect();
+        only_theirs.sort_by_key(|w| Reverse(w.len()));
+        WordComparison {
+            shared,
+            only_yours,
+            only_theirs,
+        }
+    }
+}
+
+/// Options controlling how a new `Game` is generated.
+#[derive(Debug, Clone)]
+pub struct GameConfig<'a> {
+    /// The shape of the grid.
+    pub shape: Shape,
+    /// How the squares of the grid connect to each other.
+    pub topology: Topology,
+    /// How many special squares to scatter over the grid.
+    pub modifiers: ModifierDensity,
+    /// The word list the hidden target word is drawn from, and the grid is solved against.
+    pub dictionary: &'a Dictionary,
+    /// How the tiles around the hidden target word are chosen.
+    pub distribution: LetterDistribution,
+    /// Requirements the generated board must meet.
+    pub constraints: BoardConstraints,
+    /// Which words count, and how they may be traced.
+    pub rules: GameRules,
+}
+
+impl GameConfig<'static> {
+    /// Returns the configuration for a `size` x `size` game, using the embedded dictionary.
+    pub fn new(size: usize) -> Self {
+        GameConfig {
+            shape: Shape::square(size),
+            topology: Topology::default(),
+            modifiers: ModifierDensity::default(),
+            dictionary: Dictionary::embedded(),
+            distribution: LetterDistribution::default(),
+            constraints: BoardConstraints::default(),
+            rules: GameRules::default(),
+        }
+    }
+}
+
+impl GameConfig<'_> {
+    /// Returns the side of a square board with about as many squares as this one.
+    fn square_size(&self) -> usize {
+        (self.shape.len() as f64).sqrt() as usize
+    }
+
+    /// Returns one more than the most letters a target word hidden in the board can have.
+    ///
+    /// The walk laying out a target word steps on from its last tile, so target words have to be
+    /// shorter than the largest region of the board, or they never fit.
+    fn target_length_limit(&self) -> usize {
+        (3 * self.square_size()).min(self.shape.largest_region(self.topology))
+    }
+
+    /// Returns whether the dictionary has a word the rules allow that's short enough to hide in the
+    /// board, which generating a game needs.
+    pub fn has_target_words(&self) -> bool {
+        let lengths = 1..self.target_length_limit();
+        self.dictionary
+            .words()
+            .iter()
+            .any(|w| lengths.contains(&w.chars().count()) && self.rules.allows(w, self.dictionary))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Game {
-    grid: Vec<Vec<char>>,
+    grid: Grid,
     valid_words: Vec<String>,
+    #[serde(default)]
+    solution: Solution,
+    /// The seed this game was generated from, if it was generated from one.
+    #[serde(default)]
+    seed: Option<u64>,
+    /// The word hidden in the grid when it was generated.
+    #[serde(default)]
+    target_word: Option<String>,
+    /// The path the target word was laid out along.
+    #[serde(default)]
+    target_path: Option<Path>,
+    #[serde(default)]
+    scoring: Scoring,
+    /// The id of the dictionary the game's words were drawn from.
+    #[serde(default = "default_dictionary")]
+    dictionary: String,
+    /// Metrics measured when the game was generated, zeroed for games stored before they were.
+    #[serde(default)]
+    stats: BoardStats,
+    /// Which squares of the grid are part of the board.
+    #[serde(default)]
+    mask: Mask,
+    /// How the squares of the grid connect to each other.
+    #[serde(default)]
+    topology: Topology,
+    #[serde(default)]
+    modifiers: Modifiers,
+    #[serde(default = "GameRules::legacy")]
+    rules: GameRules,
+}
+
+fn default_dictionary() -> String {
+    DEFAULT_DICTIONARY.to_string()
 }
 
 impl Default for Game {
@@ -39,96 +247,374 @@ impl Default for Game {
 
 impl Game {
     /// Creates a new `Game` of `size` x `size`, hiding `target_word` in the game.
-    pub fn from_target_word(size: usize, target_word: &str) -> Self {
-        fn calculate_valid_directions(
-            grid: &Vec<Vec<Option<char>>>,
-            (y, x): &(usize, usize),
-        ) -> Vec<GameDirections> {
-            let grid_length = grid.len();
-            let mut output = Vec::new();
+    ///
+    /// Fails if `target_word` doesn't fit in the board.
+    pub fn from_target_word(size: usize, target_word: &str) -> Result<Self, LayoutError> {
+        Self::from_target_word_with_rng(&GameConfig::new(size), target_word, &mut thread_rng())
+    }
 
-            if *y > 0 && grid[*y - 1][*x].is_none() {
-                output.push(GameDirections::Up);
-            }
-            if *y > 0 && *x > 0 && grid[*y - 1][*x - 1].is_none() {
-                output.push(GameDirections::UpLeft);
-            }
-            if *y > 0 && *x < grid_length - 1 && grid[*y - 1][*x + 1].is_none() {
-                output.push(GameDirections::UpRight);
-            }
-            if *y < grid_length - 1 && grid[*y + 1][*x].is_none() {
-                output.push(GameDirections::Down);
-            }
-            if *y < grid_length - 1 && *x > 0 && grid[*y + 1][*x - 1].is_none() {
-                output.push(GameDirections::DownLeft);
-            }
-            if *y < grid_length - 1 && *x < grid_length - 1 && grid[*y + 1][*x + 1].is_none() {
-                output.push(GameDirections::DownRight);
-            }
-            if *x > 0 && grid[*y][*x - 1].is_none() {
-                output.push(GameDirections::Left);
-            }
-            if *x < grid_length - 1 && grid[*y][*x + 1].is_none() {
-                output.push(GameDirections::Right);
-            }
-            output
+    /// Creates a new `Game` as described by `config`, hiding `target_word` in the game, drawing all
+    /// randomness from `rng`.
+    ///
+    /// The word is laid out along a random walk through the board, which is retried from a new
+    /// square when it gets stuck. Fails if none of a thousand walks fits the word.
+    pub fn from_target_word_with_rng<R: Rng + ?Sized>(
+        config: &GameConfig,
+        target_word: &str,
+        rng: &mut R,
+    ) -> Result<Self, LayoutError> {
+        /// How many walks are tried before giving up on laying out the target word.
+        const MAX_WALKS: usize = 1000;
+
+        let shape = &config.shape;
+
+        // words with letters outside the dictionary's alphabet are laid out a letter per tile
+        let target_tiles = config
+            .dictionary
+            .tiles_of(target_word)
+            .unwrap_or_else(|| target_word.chars().map(String::from).collect());
+        // see `GameConfig::target_length_limit`
+        if target_tiles.len() >= shape.largest_region(config.topology) {
+            return Err(LayoutError);
         }
 
-        let mut grid = 'outer: loop {
-            let mut grid = vec![vec![None; size]; size];
-            let start_point = (
-                (0..size).choose(&mut thread_rng()).unwrap(),
-                (0..size).choose(&mut thread_rng()).unwrap(),
-            );
+        let mut walks = 0..MAX_WALKS;
+        let (mut grid, target_path) = 'outer: loop {
+            if walks.next().is_none() {
+                return Err(LayoutError);
+            }
+            // masked out squares are filled with empty tiles up front, so they aren't walked through
+            let mut grid: Vec<Vec<Option<String>>> = (0..shape.rows)
+                .map(|y| {
+                    (0..shape.cols)
+                        .map(|x| (!shape.contains((y, x))).then(String::new))
+                        .collect()
+                })
+                .collect();
+            let mut path = Vec::with_capacity(target_tiles.len());
+            let start_point = loop {
+                let point = (
+                    (0..shape.rows).choose(rng).unwrap(),
+                    (0..shape.cols).choose(rng).unwrap(),
+                );
+                if shape.contains(point) {
+                    break point;
+                }
+            };
             let mut point = start_point;
 
-            for character in target_word.chars() {
-                grid[point.0][point.1] = Some(character);
-                match calculate_valid_directions(&grid, &point).choose(&mut thread_rng()) {
+            for tile in target_tiles.iter() {
+                grid[point.0][point.1] = Some(tile.clone());
+                path.push(point);
+                let free_neighbours: Vec<_> = config
+                    .topology
+                    .neighbours(point, shape.rows, shape.cols)
+                    .filter(|&(y, x)| grid[y][x].is_none())
+                    .collect();
+                match free_neighbours.choose(rng) {
                     None => continue 'outer,
-                    Some(GameDirections::Up) => point = (point.0 - 1, point.1),
-                    Some(GameDirections::UpLeft) => point = (point.0 - 1, point.1 - 1),
-                    Some(GameDirections::UpRight) => point = (point.0 - 1, point.1 + 1),
-                    Some(GameDirections::Down) => point = (point.0 + 1, point.1),
-                    Some(GameDirections::DownLeft) => point = (point.0 + 1, point.1 - 1),
-                    Some(GameDirections::DownRight) => point = (point.0 + 1, point.1 + 1),
-                    Some(GameDirections::Left) => point = (point.0, point.1 - 1),
-                    Some(GameDirections::Right) => point = (point.0, point.1 + 1),
+                    Some(&next) => point = next,
                 }
             }
-            break grid;
+            break (grid, path);
         };
 
-        for c in grid.iter_mut().flatten() {
-            if c.is_none() {
-                *c = Some(get_random_letter())
-            }
+        let empty: Vec<_> = grid.iter_mut().flatten().filter(|c| c.is_none()).collect();
+        let tiles = config
+            .distribution
+            .sample_tiles(config.dictionary, empty.len(), rng);
+        for (c, tile) in empty.into_iter().zip(tiles) {
+            *c = Some(tile);
         }
 
-        let grid = grid
+        let mut grid: Grid = grid
             .into_iter()
             .map(|r| r.into_iter().map(|c| c.unwrap()).collect())
             .collect();
+        let modifiers = config
+            .modifiers
+            .generate(shape, &mut grid, &target_path, rng);
+
+        let solution = solve_game(&grid, config.topology, &config.rules, config.dictionary);
+        Ok(Game {
+            valid_words: solution.words().cloned().collect(),
+            stats: BoardStats::new(&grid, &solution, config.dictionary),
+            solution,
+            grid,
+            seed: None,
+            target_word: Some(target_word.to_string()),
+            target_path: Some(target_path),
+            scoring: Scoring::default(),
+            dictionary: config.dictionary.id().to_string(),
+            mask: shape.mask.clone(),
+            topology: config.topology,
+            modifiers,
+            rules: config.rules,
+        })
+    }
+
+    /// Creates a new `Game` of `size` x `size`, from a randomly chosen seed.
+    pub fn new(size: usize) -> Self {
+        Game::from_config(&GameConfig::new(size))
+    }
+
+    /// Creates a new `Game` as described by `config`, from a randomly chosen seed.
+    pub fn from_config(config: &GameConfig) -> Self {
+        Game::generate_from_seed(config, thread_rng().gen())
+    }
+
+    /// Creates a new `Game` of `size` x `size` from `seed`.
+    ///
+    /// The same `size` and `seed` always produce the same grid and word list.
+    pub fn from_seed(size: usize, seed: u64) -> Self {
+        Game::generate_from_seed(&GameConfig::new(size), seed)
+    }
+
+    /// Creates a new `Game` of `size` x `size`, drawing all randomness from `rng`.
+    pub fn new_with_rng<R: Rng + ?Sized>(size: usize, rng: &mut R) -> Self {
+        Game::generate(&GameConfig::new(size), rng)
+    }
+
+    /// Creates a new `Game` as described by `config` from `seed`.
+    ///
+    /// The same `config` and `seed` always produce the same grid and word list, as long as the same
+    /// [`Blocklist`] is in use: boards showing a blocked word are regenerated, so installing a
+    /// different blocklist can change the board a seed produces.
+    pub fn generate_from_seed(config: &GameConfig, seed: u64) -> Self {
+        Game {
+            seed: Some(seed),
+            ..Game::generate(config, &mut ChaCha8Rng::seed_from_u64(seed))
+        }
+    }
+
+    /// Creates a new `Game` as described by `config`, drawing all randomness from `rng`.
+    ///
+    /// Boards are regenerated until one meets `config.constraints`, giving up after
+    /// `config.constraints.max_attempts` boards and returning the last one generated. Boards where
+    /// a word on the [`Blocklist`] can be read in a straight line don't meet the constraints, and
+    /// if the last board is one of them, the squares spelling blocked words are rerolled. Target
+    /// words that don't fit around the holes of the board use up an attempt too, and if none fit,
+    /// the board is generated without one.
+    ///
+    /// Panics if there's no word to hide in the board, which [`GameConfig::has_target_words`]
+    /// checks for.
+    pub fn generate<R: Rng + ?Sized>(config: &GameConfig, rng: &mut R) -> Self {
+        Game::generate_avoiding(config, Blocklist::global(), rng)
+    }
 
+    /// As [`Game::generate`], keeping the words on `blocklist` off the board.
+    fn generate_avoiding<R: Rng + ?Sized>(
+        config: &GameConfig,
+        blocklist: &Blocklist,
+        rng: &mut R,
+    ) -> Self {
+        let size = config.square_size();
+        let limit = config.target_length_limit();
+        let constraints = &config.constraints;
+        let mut attempts = 1..constraints.max_attempts.max(1);
+        loop {
+            // fall back to shorter words for dictionaries without any long enough to fill the grid
+            // the target word has to be one the rules allow, or it couldn't be found
+            let allowed = |word: &str| config.rules.allows(word, config.dictionary);
+            let target_word =
+                get_random_word_with_length_in(config.dictionary, 2 * size..limit, allowed, rng)
+                    .or_else(|| {
+                        get_random_word_with_length_in(
+                            config.dictionary,
+                            1..(2 * size).min(limit),
+                            allowed,
+                            rng,
+                        )
+                    })
+                    .expect(
+                        "dictionary has no words the rules allow short enough to hide in the grid",
+                    );
+
+            let mut game = match Game::from_target_word_with_rng(config, &target_word, rng) {
+                Ok(game) => game,
+                Err(LayoutError) if attempts.next().is_some() => continue,
+                // an empty word always fits, leaving a board with no target word
+                Err(LayoutError) => Game {
+                    target_word: None,
+                    target_path: None,
+                    ..Game::from_target_word_with_rng(config, "", rng)
+                        .expect("boards with target words have a square to spare")
+                },
+            };
+            if let Some(max) = constraints.max_repeated_tile {
+                if game.stats.max_repeated_tile > max {
+                    game.repair_repeated_tiles(config, max, rng);
+                }
+            }
+            let blocked = blocklist.is_visible_in(&game.grid, config.topology);
+            if !blocked && constraints.is_satisfied_by(&game.stats) {
+                return game;
+            }
+            if attempts.next().is_none() {
+                // boards showing a blocked word are never handed out, even once out of attempts
+                if blocked {
+                    game.repair_blocked_words(config, blocklist, rng);
+                }
+                return game;
+            }
+        }
+    }
+
+    /// Creates a `Game` of an existing `grid` rather than a generated one, solved with the topology,
+    /// rules and dictionary of `config`.
+    ///
+    /// Empty tiles are holes in the board. The game has no hidden target word or special squares.
+    pub fn from_grid(config: &GameConfig, grid: Grid) -> Self {
+        let solution = solve_game(&grid, config.topology, &config.rules, config.dictionary);
         Game {
-            valid_words: generate_wordlist_from_game(&grid),
+            valid_words: solution.words().cloned().collect(),
+            stats: BoardStats::new(&grid, &solution, config.dictionary),
+            solution,
+            mask: Shape::of_grid(&grid).mask,
             grid,
+            seed: None,
+            target_word: None,
+            target_path: None,
+            scoring: Scoring::default(),
+            dictionary: config.dictionary.id().to_string(),
+            topology: config.topology,
+            modifiers: Modifiers::new(),
+            rules: config.rules,
         }
     }
 
-    /// Creates a new `Game` of `size` x `size`
-    pub fn new(size: usize) -> Self {
-        let target_word_size = (2 * size..3 * size).choose(&mut thread_rng()).unwrap();
-        let target_word = get_random_n_length_word(target_word_size);
+    /// Rerolls tiles appearing more than `max` times in the grid, leaving the target word in place,
+    /// then solves the grid again.
+    ///
+    /// Tiles that can't be rerolled to one appearing fewer than `max` times are left as they are.
+    fn repair_repeated_tiles<R: Rng + ?Sized>(
+        &mut self,
+        config: &GameConfig,
+        max: usize,
+        rng: &mut R,
+    ) {
+        let mut counts: HashMap<String, usize> = quality::tile_counts(&self.grid)
+            .into_iter()
+            .map(|(tile, count)| (tile.to_string(), count))
+            .collect();
+        for (y, x) in self.shape().cells() {
+            // blocked squares have no tile, so aren't counted
+            if counts
+                .get(&self.grid[y][x])
+                .is_none_or(|&count| count <= max)
+                || self
+                    .target_path
+                    .as_ref()
+                    .is_some_and(|p| p.contains(&(y, x)))
+            {
+                continue;
+            }
+            let Some(tile) = config
+                .distribution
+                .sample_tiles(config.dictionary, REPAIR_CANDIDATES, rng)
+                .into_iter()
+                .find(|t| counts.get(t).copied().unwrap_or(0) < max)
+            else {
+                continue;
+            };
+            *counts.get_mut(&self.grid[y][x]).unwrap() -= 1;
+            *counts.entry(tile.clone()).or_default() += 1;
+            self.set_tile((y, x), tile);
+        }
+
+        self.solve_again(config);
+    }
+
+    /// Rerolls a square of each line of squares spelling a word on `blocklist` until none can be
+    /// read, then solves the grid again.
+    ///
+    /// Squares of the target word are only rerolled when a blocked word lies entirely along it, and
+    /// the game then has no target word. Once every square has had a chance to be rerolled, any
+    /// square still spelling a blocked word is made blank, so repairs always end.
+    fn repair_blocked_words<R: Rng + ?Sized>(
+        &mut self,
+        config: &GameConfig,
+        blocklist: &Blocklist,
+        rng: &mut R,
+    ) {
+        let mut rerolls = self.shape().len();
+        while let Some(line) = blocklist.visible_in(&self.grid, self.topology) {
+            let target_path = self.target_path.as_deref().unwrap_or_default();
+            let off_target: Vec<_> = line
+                .iter()
+                .filter(|square| !target_path.contains(square))
+                .collect();
+            let &(y, x) = match off_target.choose(rng) {
+                Some(square) => square,
+                None => {
+                    self.target_word = None;
+                    self.target_path = None;
+                    line.choose(rng).unwrap()
+                }
+            };
+            let tile = if rerolls > 0 {
+                rerolls -= 1;
+                config
+                    .distribution
+                    .sample_tiles(config.dictionary, REPAIR_CANDIDATES, rng)
+                    .into_iter()
+                    .find(|t| *t != self.grid[y][x])
+            } else {
+                None
+            };
+            // blanks are never read as part of a line, so this always makes progress
+            self.set_tile((y, x), tile.unwrap_or_else(|| WILDCARD.to_string()));
+        }
 
-        Game::from_target_word(size, &target_word)
+        self.solve_again(config);
     }
 
+    /// Puts `tile` on the `(y, x)` square, keeping the square's modifier in step with it: blanks are
+    /// marked as wildcard squares, and squares no longer holding a blank lose the mark.
+    fn set_tile(&mut self, (y, x): (usize, usize), tile: String) {
+        let blank = tile == WILDCARD;
+        if blank && self.modifiers.is_empty() {
+            let Shape { rows, cols, .. } = self.shape();
+            self.modifiers = vec![vec![None; cols]; rows];
+        }
+        if let Some(modifier) = self.modifiers.get_mut(y).map(|row| &mut row[x]) {
+            if blank {
+                *modifier = Some(Modifier::Wildcard);
+            } else if *modifier == Some(Modifier::Wildcard) {
+                *modifier = None;
+            }
+        }
+        self.grid[y][x] = tile;
+    }
+
+    /// Solves the grid again after its tiles have changed, along with the stats measured from it.
+    fn solve_again(&mut self, config: &GameConfig) {
+        self.solution = solve_game(&self.grid, self.topology, &self.rules, config.dictionary);
+        self.valid_words = self.solution.words().cloned().collect();
+        self.stats = BoardStats::new(&self.grid, &self.solution, config.dictionary);
+    }
+
+    /// Returns the number of rows of the grid, which is also the number of columns for the square
+    /// boards that make up most games.
     pub fn size(&self) -> usize {
         self.grid.len()
     }
 
-    pub fn grid(&self) -> &Vec<Vec<char>> {
+    /// Returns how the squares of this game's board connect to each other.
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    /// Returns the shape of this game's board.
+    pub fn shape(&self) -> Shape {
+        Shape {
+            rows: self.grid.len(),
+            cols: self.grid.first().map_or(0, Vec::len),
+            mask: self.mask.clone(),
+        }
+    }
+
+    pub fn grid(&self) -> &Grid {
         &self.grid
     }
 
@@ -136,23 +622,187 @@ impl Game {
         &self.valid_words
     }
 
+    /// Returns every valid word in this game, along with a path spelling it.
+    pub fn solution(&self) -> &Solution {
+        &self.solution
+    }
+
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    pub fn target_word(&self) -> Option<&str> {
+        self.target_word.as_deref()
+    }
+
+    pub fn target_path(&self) -> Option<&Path> {
+        self.target_path.as_ref()
+    }
+
+    /// Returns the id of the dictionary this game's words were drawn from.
+    pub fn dictionary(&self) -> &str {
+        &self.dictionary
+    }
+
+    /// Returns metrics describing this game's board.
+    pub fn stats(&self) -> &BoardStats {
+        &self.stats
+    }
+
+    pub fn scoring(&self) -> Scoring {
+        self.scoring
+    }
+
+    /// Sets the rule this game's words are scored by.
+    pub fn with_scoring(self, scoring: Scoring) -> Self {
+        Game { scoring, ..self }
+    }
+
+    /// Returns the special squares of this game's grid.
+    pub fn modifiers(&self) -> &Modifiers {
+        &self.modifiers
+    }
+
+    pub fn rules(&self) -> &GameRules {
+        &self.rules
+    }
+
+    /// Returns the points awarded for finding `word`, under this game's scoring rule.
+    ///
+    /// Modifiers are counted along `path`, the squares the player traced `word` through, which must
+    /// have been checked with [`Game::validate_path`]. Words found without tracing them count the
+    /// modifiers along the path recorded for them in the game's solution.
+    pub fn score_word(&self, word: &str, path: Option<&[(usize, usize)]>) -> usize {
+        let score = self.scoring.score_word(self, word);
+        match path.or_else(|| self.solution.path(word).map(Vec::as_slice)) {
+            Some(path) => modifiers::apply_modifiers(score, path, &self.grid, &self.modifiers),
+            None => score,
+        }
+    }
+
+    /// Returns whether `word` can be found in the grid, and counts under the game's rules.
     pub fn validate(&self, word: &str) -> bool {
         self.valid_words.binary_search(&word.to_string()).is_ok()
     }
 
-    pub fn score(self, mut found_words: Vec<String>) -> GameResults {
-        found_words.sort_by(|a, b| b.len().cmp(&a.len()));
+    /// Checks that `path` is a sequence of `(y, x)` squares, each adjacent to the last under the
+    /// game's topology and none used twice, spelling a valid word, and returns that word.
+    ///
+    /// A path through blank squares can spell several words, in which case
+    /// [`PathError::Ambiguous`] is returned, and [`Game::validate_path_spelling`] can be used
+    /// with the word that was meant.
+    pub fn validate_path(&self, path: &[(usize, usize)]) -> Result<String, PathError> {
+        let tiles = self.path_tiles(path)?;
+        if !tiles.contains(&WILDCARD) {
+            let word = tiles.concat();
+            if !self.validate(&word) {
+                return Err(PathError::NotAWord);
+            }
+            return Ok(word);
+        }
+        let mut words = self
+            .valid_words
+            .iter()
+            .filter(|word| tiles_spell(&tiles, word));
+        match (words.next(), words.next()) {
+            (Some(word), None) => Ok(word.clone()),
+            (Some(_), Some(_)) => Err(PathError::Ambiguous),
+            (None, _) => Err(PathError::NotAWord),
+        }
+    }
+
+    /// As [`Game::validate_path`], but checks that `path` spells `word` in particular, with its
+    /// blank squares standing for the letters of `word`.
+    pub fn validate_path_spelling(
+        &self,
+        path: &[(usize, usize)],
+        word: &str,
+    ) -> Result<String, PathError> {
+        let tiles = self.path_tiles(path)?;
+        if !tiles_spell(&tiles, word) || !self.validate(word) {
+            return Err(PathError::NotAWord);
+        }
+        Ok(word.to_string())
+    }
+
+    /// Returns the tiles along `path`, checking that it's a well formed path through the grid.
+    fn path_tiles(&self, path: &[(usize, usize)]) -> Result<Vec<&str>, PathError> {
+        if path.is_empty() {
+            return Err(PathError::Empty);
+        }
+        let Shape { rows, cols, .. } = self.shape();
+        let mut tiles = Vec::with_capacity(path.len());
+        for (i, &(y, x)) in path.iter().enumerate() {
+            let Some(tile) = self
+                .grid
+                .get(y)
+                .and_then(|row| row.get(x))
+                .filter(|tile| !tile.is_empty())
+            else {
+                return Err(PathError::OutOfBounds);
+            };
+            if !self.rules.reuse_cells && path[..i].contains(&(y, x)) {
+                return Err(PathError::ReusedSquare);
+            }
+            if let Some(&prev) = i.checked_sub(1).map(|prev| &path[prev]) {
+                if !self.topology.are_adjacent(prev, (y, x), rows, cols) {
+                    return Err(PathError::NotAdjacent);
+                }
+            }
+            tiles.push(tile.as_str());
+        }
+        // words crossing more blanks than this aren't found by the solver, so aren't valid words
+        if tiles.iter().filter(|&&t| t == WILDCARD).count() > MAX_BLANKS_PER_WORD {
+            return Err(PathError::NotAWord);
+        }
+        Ok(tiles)
+    }
+
+    /// Returns the letter each blank square stands for in the path spelling `word`, if any.
+    pub fn blanks(&self, word: &str) -> Vec<((usize, usize), char)> {
+        self.solution.blanks(word, &self.grid).unwrap_or_default()
+    }
+
+    pub fn score(self, found_words: impl IntoIterator<Item = impl Into<FoundWord>>) -> GameResults {
+        let found_words: Vec<FoundWord> = found_words.into_iter().map(Into::into).collect();
+        // words the game's rules don't allow score nothing
+        let score = found_words
+            .iter()
+            .filter(|found| self.validate(&found.word))
+            .map(|found| self.score_word(&found.word, found.path.as_deref()))
+            .sum();
+        let mut found_words: Vec<String> =
+            found_words.into_iter().map(|found| found.word).collect();
+        found_words.sort_by_key(|w| Reverse(w.len()));
+        let found_target_word = self
+            .target_word
+            .as_ref()
+            .is_some_and(|t| found_words.contains(t));
         let mut missed_words: Vec<String> = self
             .valid_words
             .into_iter()
-            .filter(|w| !found_words.contains(w))
+            // games stored before the blocklist existed can have blocked words among their words
+            .filter(|w| !found_words.contains(w) && !Blocklist::global().contains(w))
             .collect();
-        missed_words.sort_by(|a, b| b.len().cmp(&a.len()));
+        missed_words.sort_by_key(|w| Reverse(w.len()));
+        // games stored before paths were recorded don't have a solution, so solve them again. They
+        // predate other dictionaries, so any other game without one simply has no words
+        let solution = if self.solution.is_empty() && self.dictionary == DEFAULT_DICTIONARY {
+            solve_game(
+                &self.grid,
+                self.topology,
+                &self.rules,
+                Dictionary::embedded(),
+            )
+        } else {
+            self.solution
+        };
         GameResults {
-            score: found_words
-                .iter()
-                .map(|w| 2_usize.pow(w.len() as u32))
-                .sum(),
+            missed_paths: solution.filter(|w| missed_words.iter().any(|m| m == w)),
+            score,
+            target_word: self.target_word,
+            target_path: self.target_path,
+            found_target_word,
             found_words,
             missed_words,
         }
@@ -162,6 +812,38 @@ impl Game {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::words::letter_grid;
+
+    /// Asserts that the squares of `game` marked as wildcard squares are the ones holding blanks.
+    fn assert_blanks_are_marked(game: &Game) {
+        for (y, row) in game.grid().iter().enumerate() {
+            for (x, tile) in row.iter().enumerate() {
+                let modifier = game.modifiers().get(y).and_then(|row| row[x]);
+                assert_eq!(tile == WILDCARD, modifier == Some(Modifier::Wildcard));
+            }
+        }
+    }
+
+    impl Game {
+        /// Creates a `Game` of `grid` with no valid words, leaving everything else at its default.
+        fn for_test(grid: Grid) -> Self {
+            Game {
+                grid,
+                valid_words: Vec::new(),
+                solution: Solution::default(),
+                seed: None,
+                target_word: None,
+                target_path: None,
+                scoring: Scoring::default(),
+                dictionary: default_dictionary(),
+                stats: BoardStats::default(),
+                mask: Mask::default(),
+                topology: Topology::default(),
+                modifiers: Modifiers::new(),
+                rules: GameRules::default(),
+            }
+        }
+    }
 
     #[test]
     fn test_game_generation() {
@@ -172,13 +854,13 @@ mod tests {
     #[test]
     fn wordlist_validation() {
         let game = Game {
-            grid: vec![vec![]],
             valid_words: vec![
                 "bar".to_string(),
                 "baz".to_string(),
                 "foo".to_string(),
                 "qux".to_string(),
             ],
+            ..Game::for_test(vec![vec![]])
         };
         assert!(game.validate("foo"));
         assert!(game.validate("bar"));
@@ -189,4 +871,652 @@ mod tests {
         assert!(game.validate(&game.valid_words[2]));
         assert!(game.validate(&game.valid_words[game.valid_words().len() - 1]));
     }
+
+    #[test]
+    fn path_validation() {
+        let game = Game {
+            valid_words: vec!["car".to_string(), "cat".to_string(), "cats".to_string()],
+            ..Game::for_test(letter_grid(&["cat", "xrs", "xxx"]))
+        };
+        assert_eq!(
+            game.validate_path(&[(0, 0), (0, 1), (0, 2)]),
+            Ok("cat".to_string())
+        );
+        assert_eq!(
+            game.validate_path(&[(0, 0), (0, 1), (0, 2), (1, 2)]),
+            Ok("cats".to_string())
+        );
+        assert_eq!(game.validate_path(&[]), Err(PathError::Empty));
+        assert_eq!(
+            game.validate_path(&[(0, 0), (0, 3)]),
+            Err(PathError::OutOfBounds)
+        );
+        assert_eq!(
+            game.validate_path(&[(0, 0), (0, 2), (0, 1)]),
+            Err(PathError::NotAdjacent)
+        );
+        assert_eq!(
+            game.validate_path(&[(0, 1), (0, 0), (0, 1)]),
+            Err(PathError::ReusedSquare)
+        );
+        assert_eq!(
+            game.validate_path(&[(0, 2), (0, 1), (1, 1)]),
+            Err(PathError::NotAWord)
+        );
+
+        let game = Game::from_seed(5, 3);
+        for word in game.solution().words() {
+            let path = game.solution().path(word).unwrap();
+            assert_eq!(game.validate_path(path).as_ref(), Ok(word));
+        }
+    }
+
+    #[test]
+    fn wildcard_path_validation() {
+        let grid = letter_grid(&["c*t", "xrs", "xxx"]);
+        let solution = solve_game(
+            &grid,
+            Topology::Bounded,
+            &GameRules::default(),
+            Dictionary::embedded(),
+        );
+        let game = Game {
+            valid_words: solution.words().cloned().collect(),
+            solution,
+            scoring: Scoring::LetterValue,
+            ..Game::for_test(grid)
+        };
+        // "cat", "cot" and "cut" all fit
+        assert_eq!(
+            game.validate_path(&[(0, 0), (0, 1), (0, 2)]),
+            Err(PathError::Ambiguous)
+        );
+        assert_eq!(
+            game.validate_path_spelling(&[(0, 0), (0, 1), (0, 2)], "cot"),
+            Ok("cot".to_string())
+        );
+        assert_eq!(
+            game.validate_path_spelling(&[(0, 0), (0, 1), (0, 2)], "cart"),
+            Err(PathError::NotAWord)
+        );
+        assert_eq!(
+            game.validate_path(&[(1, 0), (1, 1), (0, 1)]),
+            Err(PathError::NotAWord)
+        );
+
+        // the blank is worth nothing under letter values
+        assert_eq!(game.blanks("cot"), [((0, 1), 'o')]);
+        assert_eq!(game.score_word("cot", None), 4);
+    }
+
+    #[test]
+    fn target_word_is_remembered() {
+        let game = Game::from_target_word(5, "wordtwist").unwrap();
+        let path = game.target_path().unwrap();
+        let spelled: String = path
+            .iter()
+            .map(|&(y, x)| game.grid[y][x].as_str())
+            .collect();
+        assert_eq!(game.target_word(), Some("wordtwist"));
+        assert_eq!(spelled, "wordtwist");
+
+        let game = Game::from_seed(4, 10);
+        let target = game.target_word().unwrap().to_string();
+        assert_eq!(
+            game.validate_path(game.target_path().unwrap()),
+            Ok(target.clone())
+        );
+
+        let results = game.score(vec![target.clone()]);
+        assert!(results.found_target_word);
+        assert_eq!(results.target_word, Some(target));
+        assert!(
+            !Game::from_seed(4, 10)
+                .score(Vec::<String>::new())
+                .found_target_word
+        );
+    }
+
+    #[test]
+    fn compare_found_words() {
+        let game = Game::from_grid(
+            &GameConfig::new(3),
+            vec![
+                vec!["c".to_string(), "a".to_string(), "t".to_string()],
+                vec!["o".to_string(), "r".to_string(), "s".to_string()],
+                vec!["w".to_string(), "e".to_string(), "d".to_string()],
+            ],
+        );
+        let results = game.score(vec!["cat".to_string(), "cart".to_string()]);
+        let comparison =
+            results.compare(&["cat".to_string(), "sat".to_string(), "cars".to_string()]);
+        assert_eq!(
+            comparison,
+            WordComparison {
+                shared: vec!["cat".to_string()],
+                only_yours: vec!["cart".to_string()],
+                only_theirs: vec!["cars".to_string(), "sat".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn missed_words_have_paths() {
+        let game = Game::from_seed(4, 99);
+        let found = vec![game.valid_words()[0].clone()];
+        let results = game.score(found.clone());
+
+        assert_eq!(results.missed_paths.len(), results.missed_words.len());
+        assert!(results
+            .missed_words
+            .iter()
+            .all(|w| results.missed_paths.contains(w)));
+        assert!(!results.missed_paths.contains(&found[0]));
+    }
+
+    #[test]
+    fn generation_uses_chosen_dictionary() {
+        let dictionary = Dictionary::new("small", ["abracadabra", "cab", "bad", "dab"]);
+        let config = GameConfig {
+            dictionary: &dictionary,
+            ..GameConfig::new(4)
+        };
+        let game = Game::generate_from_seed(&config, 5);
+
+        assert_eq!(game.dictionary(), "small");
+        assert_eq!(game.target_word(), Some("abracadabra"));
+        assert!(game.validate("abracadabra"));
+        assert!(game.valid_words().iter().all(|w| dictionary.contains(w)));
+    }
+
+    #[test]
+    fn generation_uses_chosen_distribution() {
+        let config = GameConfig {
+            distribution: LetterDistribution::Weights("z:1".parse().unwrap()),
+            ..GameConfig::new(4)
+        };
+        let game = Game::generate_from_seed(&config, 5);
+        let path = game.target_path().unwrap();
+
+        for (y, row) in game.grid().iter().enumerate() {
+            for (x, tile) in row.iter().enumerate() {
+                if !path.contains(&(y, x)) {
+                    assert_eq!(tile, "z");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn generation_meets_constraints() {
+        let config = GameConfig {
+            constraints: BoardConstraints {
+                min_words: 40,
+                max_words: Some(120),
+                min_long_words: 3,
+                ..Default::default()
+            },
+            ..GameConfig::new(4)
+        };
+        for seed in 0..5 {
+            let game = Game::generate_from_seed(&config, seed);
+            assert!(config.constraints.is_satisfied_by(game.stats()));
+            assert_eq!(
+                game.stats(),
+                &BoardStats::new(game.grid(), game.solution(), Dictionary::embedded())
+            );
+        }
+    }
+
+    #[test]
+    fn generation_meets_difficulty() {
+        for difficulty in [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard] {
+            let config = GameConfig {
+                constraints: BoardConstraints {
+                    difficulty: Some(difficulty),
+                    ..Default::default()
+                },
+                ..GameConfig::new(4)
+            };
+            let game = Game::generate_from_seed(&config, 0);
+            assert!(difficulty.band().contains(&game.stats().difficulty));
+        }
+    }
+
+    #[test]
+    fn repeated_tiles_are_repaired() {
+        // every cell would be "e" outside the target word without repairs
+        let config = GameConfig {
+            distribution: LetterDistribution::Weights("e:1000,t:1,s:1,r:1".parse().unwrap()),
+            constraints: BoardConstraints {
+                max_repeated_tile: Some(6),
+                ..Default::default()
+            },
+            ..GameConfig::new(4)
+        };
+        for seed in 0..5 {
+            let game = Game::generate_from_seed(&config, seed);
+            assert!(game.stats().max_repeated_tile <= 6);
+            assert!(game
+                .valid_words()
+                .iter()
+                .all(|w| game.solution().contains(w)));
+        }
+
+        // blanks rerolled to letters are no longer wildcard squares
+        let config = GameConfig {
+            modifiers: ModifierDensity {
+                wildcard: 0.4,
+                ..Default::default()
+            },
+            constraints: BoardConstraints {
+                max_repeated_tile: Some(3),
+                ..Default::default()
+            },
+            ..GameConfig::new(4)
+        };
+        for seed in 0..3 {
+            let game = Game::generate_from_seed(&config, seed);
+            assert!(game.stats().max_repeated_tile <= 3);
+            assert_blanks_are_marked(&game);
+        }
+    }
+
+    #[test]
+    fn shaped_generation() {
+        let shapes = [
+            Shape {
+                rows: 3,
+                cols: 6,
+                mask: Mask::Full,
+            },
+            Shape {
+                rows: 5,
+                cols: 5,
+                mask: Mask::Diamond,
+            },
+            Shape {
+                rows: 3,
+                cols: 3,
+                mask: Mask::Cross,
+            },
+            Shape {
+                rows: 4,
+                cols: 4,
+                mask: Mask::Holes(vec![(1, 1), (2, 2)]),
+            },
+        ];
+        for shape in shapes {
+            let config = GameConfig {
+                shape: shape.clone(),
+                ..GameConfig::new(4)
+            };
+            let game = Game::generate_from_seed(&config, 11);
+            assert_eq!(game.shape(), shape);
+            assert_eq!(
+                game.validate_path(&[(3, 3), (9, 9)]),
+                Err(PathError::OutOfBounds)
+            );
+            if let Mask::Holes(holes) = &shape.mask {
+                assert_eq!(game.validate_path(&holes[..1]), Err(PathError::OutOfBounds));
+            }
+            assert_eq!(game.grid().len(), shape.rows);
+            for (y, row) in game.grid().iter().enumerate() {
+                assert_eq!(row.len(), shape.cols);
+                for (x, tile) in row.iter().enumerate() {
+                    assert_eq!(tile.is_empty(), !shape.contains((y, x)));
+                }
+            }
+            for word in game.valid_words() {
+                let path = game.solution().path(word).unwrap();
+                assert!(path.iter().all(|&square| shape.contains(square)));
+                assert_eq!(game.validate_path(path).as_deref(), Ok(word.as_str()));
+            }
+            let target_path = game.target_path().unwrap();
+            assert!(target_path.iter().all(|&square| shape.contains(square)));
+        }
+    }
+
+    #[test]
+    fn split_shapes() {
+        // holes in every other column leave columns of three squares, too short for any target word
+        let columns = GameConfig {
+            shape: Shape {
+                rows: 3,
+                cols: 7,
+                mask: Mask::parse_holes("0,1;0,3;0,5;1,1;1,3;1,5;2,1;2,3;2,5").unwrap(),
+            },
+            ..GameConfig::new(3)
+        };
+        assert!(!columns.has_target_words());
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        assert_eq!(
+            Game::from_target_word_with_rng(&columns, "cat", &mut rng),
+            Err(LayoutError)
+        );
+
+        // holes in two columns leave islands of six squares, which fit words of up to five letters
+        let islands = GameConfig {
+            shape: Shape {
+                rows: 3,
+                cols: 8,
+                mask: Mask::parse_holes("0,2;1,2;2,2;0,5;1,5;2,5").unwrap(),
+            },
+            ..GameConfig::new(3)
+        };
+        assert!(islands.has_target_words());
+        for seed in 0..5 {
+            let game = Game::generate_from_seed(&islands, seed);
+            assert!(game.target_word().unwrap().chars().count() < 6);
+        }
+    }
+
+    #[test]
+    fn torus_generation() {
+        let config = GameConfig {
+            topology: Topology::Torus,
+            ..GameConfig::new(4)
+        };
+        let mut wrapping_words = 0;
+        for seed in 0..5 {
+            let game = Game::generate_from_seed(&config, seed);
+            assert_eq!(game.topology(), Topology::Torus);
+            for word in game.valid_words() {
+                let path = game.solution().path(word).unwrap();
+                assert_eq!(game.validate_path(path).as_deref(), Ok(word.as_str()));
+            }
+            let target_path = game.target_path().unwrap();
+            assert_eq!(
+                game.validate_path(target_path).as_deref(),
+                Ok(game.target_word().unwrap())
+            );
+
+            let bounded = solve_game(
+                game.grid(),
+                Topology::Bounded,
+                &GameRules::default(),
+                Dictionary::embedded(),
+            );
+            assert!(bounded.words().all(|w| game.solution().contains(w)));
+            wrapping_words += game.solution().len() - bounded.len();
+        }
+        assert!(wrapping_words > 0);
+    }
+
+    #[test]
+    fn hex_generation() {
+        let config = GameConfig {
+            shape: Shape {
+                rows: 5,
+                cols: 5,
+                mask: Mask::Hexagon,
+            },
+            topology: Topology::Hex,
+            ..GameConfig::new(5)
+        };
+        for seed in 0..5 {
+            let game = Game::generate_from_seed(&config, seed);
+            assert_eq!(game.topology(), Topology::Hex);
+            assert_eq!(
+                game.grid()
+                    .iter()
+                    .flatten()
+                    .filter(|t| !t.is_empty())
+                    .count(),
+                19
+            );
+            for word in game.valid_words() {
+                let path = game.solution().path(word).unwrap();
+                assert_eq!(game.validate_path(path).as_deref(), Ok(word.as_str()));
+            }
+            let target_path = game.target_path().unwrap();
+            assert_eq!(
+                game.validate_path(target_path).as_deref(),
+                Ok(game.target_word().unwrap())
+            );
+            // squares diagonal on the grid are two hexagons apart
+            assert_eq!(
+                game.validate_path(&[(2, 2), (3, 3)]),
+                Err(PathError::NotAdjacent)
+            );
+        }
+    }
+
+    #[test]
+    fn game_from_grid() {
+        let mut grid = letter_grid(&["cat", "xrs", "xxx"]);
+        grid[2][2].clear();
+        let game = Game::from_grid(&GameConfig::new(3), grid.clone());
+        assert_eq!(game.grid(), &grid);
+        assert_eq!(game.shape(), Shape::of_grid(&grid));
+        assert!(game.validate("cats"));
+        assert_eq!(game.stats().total_words, game.valid_words().len());
+        assert_eq!(game.target_word(), None);
+        assert_eq!(
+            game.validate_path(&[(0, 0), (0, 1), (1, 1)]),
+            Ok("car".to_string())
+        );
+    }
+
+    #[test]
+    fn blocked_words_are_left_out() {
+        let blocklist = Blocklist::global();
+        for seed in 0..50 {
+            let game = Game::generate_from_seed(&GameConfig::new(3), seed);
+            assert!(!blocklist.is_visible_in(game.grid(), game.topology()));
+            assert!(!blocklist.contains(game.target_word().unwrap()));
+            assert!(game.valid_words().iter().all(|w| !blocklist.contains(w)));
+        }
+
+        let grid = letter_grid(&["fag", "xgx", "xxx"]);
+        let solution = solve_game(
+            &grid,
+            Topology::Bounded,
+            &GameRules::legacy(),
+            Dictionary::embedded(),
+        );
+        assert!(!solution.contains("fag"));
+        assert!(solution.contains("gag"));
+    }
+
+    #[test]
+    fn blocked_words_are_repaired() {
+        // common lines of tiles are seen on almost every board, so boards are often rejected
+        let blocklist = Blocklist::new(["er", "re", "es", "se"]);
+        for seed in 0..5 {
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
+            let game = Game::generate_avoiding(&GameConfig::new(5), &blocklist, &mut rng);
+            assert!(!blocklist.is_visible_in(game.grid(), game.topology()));
+        }
+
+        // every board is rejected, so the last one has to be repaired
+        let blocklist = Blocklist::new(["ho", "oh", "hh", "oo"]);
+        let config = GameConfig {
+            distribution: LetterDistribution::Weights("h:1,o:1".parse().unwrap()),
+            constraints: BoardConstraints {
+                max_attempts: 5,
+                ..Default::default()
+            },
+            ..GameConfig::new(4)
+        };
+        for seed in 0..5 {
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
+            let game = Game::generate_avoiding(&config, &blocklist, &mut rng);
+            assert!(!blocklist.is_visible_in(game.grid(), game.topology()));
+            assert_blanks_are_marked(&game);
+            assert_eq!(
+                *game.valid_words(),
+                solve_game(
+                    game.grid(),
+                    game.topology(),
+                    game.rules(),
+                    Dictionary::embedded()
+                )
+                .words()
+                .cloned()
+                .collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn rules_are_applied() {
+        let config = GameConfig {
+            rules: GameRules {
+                min_word_length: 4,
+                exclude_plurals: true,
+                ..Default::default()
+            },
+            ..GameConfig::new(4)
+        };
+        let game = Game::generate_from_seed(&config, 8);
+        let dictionary = Dictionary::embedded();
+        assert_eq!(game.rules(), &config.rules);
+        assert!(config.rules.allows(game.target_word().unwrap(), dictionary));
+        assert!(game
+            .valid_words()
+            .iter()
+            .all(|w| w.len() >= 4 && !dictionary.is_plural(w)));
+        let default = Game::generate_from_seed(&GameConfig::new(4), 8);
+        let short = default.valid_words().iter().find(|w| w.len() == 3).unwrap();
+        assert!(!game.validate(short));
+        let results = game.score(vec![short.clone()]);
+        assert_eq!(results.score, 0);
+
+        // no word that long fits in the board, so no game can be generated
+        assert!(config.has_target_words());
+        let too_long = GameConfig {
+            rules: GameRules {
+                min_word_length: 25,
+                ..Default::default()
+            },
+            ..GameConfig::new(7)
+        };
+        assert!(!too_long.has_target_words());
+    }
+
+    #[test]
+    fn reused_cells() {
+        let config = GameConfig {
+            rules: GameRules {
+                reuse_cells: true,
+                ..Default::default()
+            },
+            ..GameConfig::new(4)
+        };
+        let game = Game::generate_from_seed(&config, 5);
+        let default = Game::generate_from_seed(&GameConfig::new(4), 5);
+        assert_eq!(game.grid(), default.grid());
+        assert!(game.valid_words().len() > default.valid_words().len());
+
+        let reusing = game
+            .valid_words()
+            .iter()
+            .find(|w| {
+                let path = game.solution().path(w).unwrap();
+                (1..path.len()).any(|i| path[..i].contains(&path[i]))
+            })
+            .unwrap();
+        let path = game.solution().path(reusing).unwrap();
+        assert_eq!(game.validate_path(path).as_ref(), Ok(reusing));
+        assert_eq!(default.validate_path(path), Err(PathError::ReusedSquare));
+    }
+
+    #[test]
+    fn wildcards_are_generated_and_solved() {
+        let config = GameConfig {
+            modifiers: ModifierDensity {
+                wildcard: 0.1,
+                ..Default::default()
+            },
+            ..GameConfig::new(5)
+        };
+        let game = Game::generate_from_seed(&config, 4);
+        let blanks = game
+            .grid()
+            .iter()
+            .flatten()
+            .filter(|t| *t == WILDCARD)
+            .count();
+        assert!(blanks > 0);
+        assert!(game.valid_words().iter().all(|w| !w.contains(WILDCARD)));
+        assert!(game
+            .valid_words()
+            .iter()
+            .any(|word| !game.blanks(word).is_empty()));
+        for word in game.valid_words() {
+            let path = game.solution().path(word).unwrap();
+            assert_eq!(game.validate_path_spelling(path, word).as_ref(), Ok(word));
+        }
+    }
+
+    #[test]
+    fn modifiers_are_generated_and_scored() {
+        let config = GameConfig {
+            modifiers: ModifierDensity {
+                double_letter: 0.2,
+                triple_word: 0.1,
+                blocked: 0.2,
+                ..Default::default()
+            },
+            ..GameConfig::new(5)
+        };
+        let game = Game::generate_from_seed(&config, 2);
+        let modifier = |(y, x): (usize, usize)| game.modifiers()[y][x];
+
+        let target_path = game.target_path().unwrap();
+        assert!(target_path
+            .iter()
+            .all(|&square| modifier(square) != Some(Modifier::Blocked)));
+        let mut modified_words = 0;
+        for word in game.valid_words() {
+            let path = game.solution().path(word).unwrap();
+            assert!(path
+                .iter()
+                .all(|&square| modifier(square) != Some(Modifier::Blocked)));
+            let base = game.scoring().score_word(&game, word);
+            if path.iter().any(|&square| modifier(square).is_some()) {
+                assert!(game.score_word(word, Some(path)) > base);
+                modified_words += 1;
+            } else {
+                assert_eq!(game.score_word(word, None), base);
+            }
+        }
+        assert!(modified_words > 0);
+        assert!(Game::from_seed(5, 2).modifiers().is_empty());
+    }
+
+    #[test]
+    fn traced_paths_are_scored() {
+        // "cat" can be traced through either "a", and only the top one triples the word
+        let game = Game {
+            valid_words: vec!["cat".to_string()],
+            modifiers: vec![
+                vec![None, Some(Modifier::TripleWord), None],
+                vec![None, None, None],
+            ],
+            ..Game::for_test(letter_grid(&["cat", "xax"]))
+        };
+        let top = vec![(0, 0), (0, 1), (0, 2)];
+        let bottom = vec![(0, 0), (1, 1), (0, 2)];
+        assert_eq!(game.score_word("cat", Some(&top)), 24);
+        assert_eq!(game.score_word("cat", Some(&bottom)), 8);
+
+        let traced = |path: &Path| FoundWord {
+            word: "cat".to_string(),
+            path: Some(path.clone()),
+        };
+        assert_eq!(game.clone().score([traced(&top)]).score, 24);
+        assert_eq!(game.score([traced(&bottom)]).score, 8);
+    }
+
+    #[test]
+    fn seeded_generation_is_reproducible() {
+        for size in 3..=7 {
+            let game = Game::from_seed(size, 1234);
+            assert_eq!(game, Game::from_seed(size, 1234));
+            assert_eq!(game.seed(), Some(1234));
+        }
+        assert_ne!(Game::from_seed(5, 1).grid, Game::from_seed(5, 2).grid);
+    }
 }
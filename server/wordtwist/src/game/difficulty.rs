@@ -0,0 +1,124 @@
+use std::{collections::HashMap, ops::RangeInclusive};
+
+use serde::{Deserialize, Serialize};
+
+use crate::words::{Dictionary, Grid, Solution, WILDCARD};
+
+/// How hard a board is to play, as a band of difficulty scores.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    /// Returns the range of scores, as computed by [`difficulty_score`], boards in this band have.
+    ///
+    /// The bands are the same at every size. Most randomly generated boards are hard, and few large
+    /// boards are easy, so generating an easy large board can take many attempts.
+    pub fn band(self) -> RangeInclusive<usize> {
+        match self {
+            Difficulty::Easy => 0..=39,
+            Difficulty::Medium => 40..=46,
+            Difficulty::Hard => 47..=100,
+        }
+    }
+
+    /// Returns the band containing `score`.
+    pub fn of_score(score: usize) -> Self {
+        [Difficulty::Easy, Difficulty::Medium]
+            .into_iter()
+            .find(|d| d.band().contains(&score))
+            .unwrap_or(Difficulty::Hard)
+    }
+}
+
+/// Scores how hard the board `grid`, whose valid words are `solution`, is to play, from 0 (easiest)
+/// to 100 (hardest).
+///
+/// The score combines how few words there are for the size of the grid, how long those words are
+/// on average, how rare those words are, and how rare the tiles in the grid are. There is no word
+/// frequency list to measure word rarity by, so it's approximated by the rarity of the tiles along
+/// each word's path. Tile rarity is relative to how often each tile appears across `dictionary`.
+pub fn difficulty_score(grid: &Grid, solution: &Solution, dictionary: &Dictionary) -> usize {
+    let frequencies: HashMap<&str, usize> = dictionary
+        .tile_frequencies()
+        .iter()
+        .map(|(tile, count)| (tile.as_str(), *count))
+        .collect();
+    let most_frequent = frequencies.values().copied().max().unwrap_or(0).max(1);
+    // 0 for the most common tile, approaching 1 for tiles that hardly appear in the dictionary.
+    // Blanks can be any letter, so they're never awkward.
+    let rarity = |tile: &str| {
+        if tile == WILDCARD {
+            return 0.0;
+        }
+        let frequency = frequencies.get(tile).copied().unwrap_or(0);
+        1.0 - frequency as f64 / most_frequent as f64
+    };
+    let mean = |values: Vec<f64>| {
+        if values.is_empty() {
+            1.0
+        } else {
+            values.iter().sum::<f64>() / values.len() as f64
+        }
+    };
+
+    let tiles: Vec<&String> = grid.iter().flatten().filter(|t| !t.is_empty()).collect();
+    let cells = tiles.len().max(1);
+    // typical boards have between 2 and 10 words per cell
+    let words_per_cell = solution.len() as f64 / cells as f64;
+    let scarcity = 1.0 - (words_per_cell.ln() / 12_f64.ln()).clamp(0.0, 1.0);
+    let word_length = mean(solution.words().map(|w| w.chars().count() as f64).collect());
+    let length = (word_length - 3.5).clamp(0.0, 1.0);
+    let word_rarity = mean(
+        solution
+            .words()
+            .map(|w| {
+                let path = solution.path(w).unwrap();
+                mean(path.iter().map(|&(y, x)| rarity(&grid[y][x])).collect())
+            })
+            .collect(),
+    );
+    let awkwardness = mean(tiles.iter().map(|t| rarity(t)).collect());
+
+    let score = 0.5 * scarcity + 0.2 * length + 0.15 * word_rarity + 0.15 * awkwardness;
+    (score * 100.0).round() as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        game::GameRules,
+        words::{letter_grid, solve_game, Topology},
+    };
+
+    fn score(rows: &[&str]) -> usize {
+        let grid = letter_grid(rows);
+        let dictionary = Dictionary::embedded();
+        difficulty_score(
+            &grid,
+            &solve_game(&grid, Topology::Bounded, &GameRules::default(), dictionary),
+            dictionary,
+        )
+    }
+
+    #[test]
+    fn awkward_boards_are_harder() {
+        let easy = score(&["stea", "rlin", "etas", "pord"]);
+        let hard = score(&["qzxj", "vkwy", "jzqx", "fvkw"]);
+        assert!(easy < hard);
+        assert!(Difficulty::Hard.band().contains(&hard));
+        assert!(Difficulty::Easy.band().contains(&easy));
+    }
+
+    #[test]
+    fn bands_cover_every_score() {
+        for score in 0..=100 {
+            assert!(Difficulty::of_score(score).band().contains(&score));
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use super::{LetterValue, Shape};
+use crate::words::{Grid, Path, WILDCARD};
+
+/// A special square, changing how words crossing it are scored, or whether it can be used at all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Modifier {
+    /// Words crossing the square earn the Scrabble value of its tile again, on top of their score.
+    DoubleLetter,
+    /// Words crossing the square score triple.
+    TripleWord,
+    /// The square is a wall, with no tile, which words can't cross.
+    Blocked,
+    /// The square holds a blank [`WILDCARD`] tile, which stands for any letter.
+    Wildcard,
+}
+
+/// The modifier on each square of a grid, if any. Empty when a game has no modifiers at all.
+pub type Modifiers = Vec<Vec<Option<Modifier>>>;
+
+/// The share of squares given each modifier when generating a game, each between 0 and 1.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ModifierDensity {
+    pub double_letter: f64,
+    pub triple_word: f64,
+    /// Squares on the hidden target word's path are never blocked.
+    pub blocked: f64,
+    pub wildcard: f64,
+}
+
+impl ModifierDensity {
+    /// Returns whether no modifiers would be generated at these densities.
+    pub fn is_none(&self) -> bool {
+        self.double_letter <= 0.0
+            && self.triple_word <= 0.0
+            && self.blocked <= 0.0
+            && self.wildcard <= 0.0
+    }
+
+    /// Scatters modifiers over the squares of `shape`, using `rng`, clearing the tiles of blocked
+    /// squares in `grid` and blanking the tiles of wildcard squares.
+    pub(crate) fn generate<R: Rng + ?Sized>(
+        &self,
+        shape: &Shape,
+        grid: &mut Grid,
+        target_path: &Path,
+        rng: &mut R,
+    ) -> Modifiers {
+        if self.is_none() {
+            return Modifiers::new();
+        }
+        let mut modifiers = vec![vec![None; shape.cols]; shape.rows];
+        for (y, x) in shape.cells() {
+            let roll: f64 = rng.gen();
+            modifiers[y][x] = if roll < self.blocked {
+                if target_path.contains(&(y, x)) {
+                    continue;
+                }
+                grid[y][x].clear();
+                Some(Modifier::Blocked)
+            } else if roll < self.blocked + self.double_letter {
+                Some(Modifier::DoubleLetter)
+            } else if roll < self.blocked + self.double_letter + self.triple_word {
+                Some(Modifier::TripleWord)
+            } else if roll < self.blocked + self.double_letter + self.triple_word + self.wildcard {
+                WILDCARD.clone_into(&mut grid[y][x]);
+                Some(Modifier::Wildcard)
+            } else {
+                None
+            };
+        }
+        modifiers
+    }
+}
+
+/// Applies the modifiers on the squares of `path` to `score`, the score of the word it spells.
+pub(crate) fn apply_modifiers(
+    score: usize,
+    path: &[(usize, usize)],
+    grid: &Grid,
+    modifiers: &Modifiers,
+) -> usize {
+    let modifier = |&(y, x): &(usize, usize)| modifiers.get(y).and_then(|row| row[x]);
+    let bonus: usize = path
+        .iter()
+        .filter(|square| modifier(square) == Some(Modifier::DoubleLetter))
+        .flat_map(|&(y, x)| grid[y][x].chars())
+        .map(LetterValue::letter_value)
+        .sum();
+    let triples = path
+        .iter()
+        .filter(|square| modifier(square) == Some(Modifier::TripleWord))
+        .count();
+    (score + bonus) * 3_usize.pow(triples as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+    use crate::words::letter_grid;
+
+    #[test]
+    fn density() {
+        let shape = Shape::square(100);
+        let mut grid = vec![vec!["e".to_string(); 100]; 100];
+        let density = ModifierDensity {
+            double_letter: 0.1,
+            triple_word: 0.05,
+            blocked: 0.2,
+            wildcard: 0.05,
+        };
+        let target_path = vec![(0, 0), (0, 1)];
+        let modifiers = density.generate(
+            &shape,
+            &mut grid,
+            &target_path,
+            &mut ChaCha8Rng::seed_from_u64(0),
+        );
+        let share = |modifier| {
+            modifiers
+                .iter()
+                .flatten()
+                .filter(|m| **m == Some(modifier))
+                .count() as f64
+                / 10_000.0
+        };
+        assert!((share(Modifier::DoubleLetter) - 0.1).abs() < 0.01);
+        assert!((share(Modifier::TripleWord) - 0.05).abs() < 0.01);
+        assert!((share(Modifier::Blocked) - 0.2).abs() < 0.01);
+        assert!((share(Modifier::Wildcard) - 0.05).abs() < 0.01);
+
+        for (y, x) in shape.cells() {
+            assert_eq!(
+                grid[y][x].is_empty(),
+                modifiers[y][x] == Some(Modifier::Blocked)
+            );
+            assert_eq!(
+                grid[y][x] == WILDCARD,
+                modifiers[y][x] == Some(Modifier::Wildcard)
+            );
+        }
+        assert!(target_path.iter().all(|&(y, x)| !grid[y][x].is_empty()));
+        assert!(ModifierDensity::default()
+            .generate(
+                &shape,
+                &mut grid,
+                &target_path,
+                &mut ChaCha8Rng::seed_from_u64(0)
+            )
+            .is_empty());
+    }
+
+    #[test]
+    fn modified_scores() {
+        let grid = letter_grid(&["cat", "xqx"]);
+        let modifiers = vec![
+            vec![None, Some(Modifier::TripleWord), None],
+            vec![None, Some(Modifier::DoubleLetter), None],
+        ];
+        let path = vec![(0, 0), (0, 1), (0, 2)];
+        assert_eq!(apply_modifiers(8, &path, &grid, &modifiers), 24);
+        let path = vec![(0, 0), (1, 1), (0, 2)];
+        assert_eq!(apply_modifiers(8, &path, &grid, &modifiers), 18);
+        assert_eq!(apply_modifiers(8, &path, &grid, &Modifiers::new()), 8);
+    }
+}
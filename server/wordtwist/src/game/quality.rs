@@ -0,0 +1,141 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{difficulty_score, Difficulty};
+use crate::words::{Dictionary, Grid, Solution};
+
+/// Words at least this many characters long count towards [`BoardStats::long_words`].
+pub const LONG_WORD_LENGTH: usize = 5;
+
+/// Metrics describing how good a generated board is to play.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BoardStats {
+    /// The number of valid words in the board.
+    pub total_words: usize,
+    /// The number of valid words at least [`LONG_WORD_LENGTH`] characters long.
+    pub long_words: usize,
+    /// The most times any single tile appears in the grid.
+    pub max_repeated_tile: usize,
+    /// How hard the board is to play, as computed by [`difficulty_score`].
+    pub difficulty: usize,
+}
+
+impl BoardStats {
+    /// Measures the board `grid`, whose valid words are `solution` from `dictionary`.
+    pub fn new(grid: &Grid, solution: &Solution, dictionary: &Dictionary) -> Self {
+        BoardStats {
+            total_words: solution.len(),
+            long_words: solution
+                .words()
+                .filter(|w| w.chars().count() >= LONG_WORD_LENGTH)
+                .count(),
+            max_repeated_tile: tile_counts(grid).into_values().max().unwrap_or(0),
+            difficulty: difficulty_score(grid, solution, dictionary),
+        }
+    }
+}
+
+/// Returns how many times each tile appears in `grid`, ignoring masked out squares.
+pub(crate) fn tile_counts(grid: &Grid) -> HashMap<&str, usize> {
+    let mut counts = HashMap::new();
+    for tile in grid.iter().flatten().filter(|t| !t.is_empty()) {
+        *counts.entry(tile.as_str()).or_default() += 1;
+    }
+    counts
+}
+
+/// Requirements a generated board must meet, checked against its [`BoardStats`].
+///
+/// Boards that don't meet them are repaired where possible, and otherwise regenerated, up to
+/// `max_attempts` times.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BoardConstraints {
+    pub min_words: usize,
+    pub max_words: Option<usize>,
+    /// The fewest words at least [`LONG_WORD_LENGTH`] characters long.
+    pub min_long_words: usize,
+    /// The most times any single tile may appear in the grid.
+    pub max_repeated_tile: Option<usize>,
+    /// The band the board's difficulty score must fall within.
+    pub difficulty: Option<Difficulty>,
+    /// How many boards to generate before settling for the last one, even if it doesn't meet the
+    /// constraints.
+    pub max_attempts: usize,
+}
+
+impl Default for BoardConstraints {
+    fn default() -> Self {
+        BoardConstraints {
+            min_words: 0,
+            max_words: None,
+            min_long_words: 0,
+            max_repeated_tile: None,
+            difficulty: None,
+            max_attempts: 100,
+        }
+    }
+}
+
+impl BoardConstraints {
+    /// Returns whether a board measuring `stats` meets every constraint.
+    pub fn is_satisfied_by(&self, stats: &BoardStats) -> bool {
+        stats.total_words >= self.min_words
+            && self.max_words.is_none_or(|max| stats.total_words <= max)
+            && stats.long_words >= self.min_long_words
+            && self
+                .max_repeated_tile
+                .is_none_or(|max| stats.max_repeated_tile <= max)
+            && self
+                .difficulty
+                .is_none_or(|d| d.band().contains(&stats.difficulty))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        game::GameRules,
+        words::{letter_grid, solve_game, Dictionary, Topology},
+    };
+
+    #[test]
+    fn stats_and_constraints() {
+        let grid = letter_grid(&["cat", "xrs", "xxx"]);
+        let dictionary = Dictionary::embedded();
+        let stats = BoardStats::new(
+            &grid,
+            &solve_game(&grid, Topology::Bounded, &GameRules::default(), dictionary),
+            dictionary,
+        );
+        assert_eq!(stats.max_repeated_tile, 4);
+        assert!(stats.total_words > 0);
+        assert!(stats.long_words < stats.total_words);
+
+        assert!(BoardConstraints::default().is_satisfied_by(&stats));
+        let constraints = BoardConstraints {
+            max_repeated_tile: Some(3),
+            ..Default::default()
+        };
+        assert!(!constraints.is_satisfied_by(&stats));
+        let constraints = BoardConstraints {
+            min_words: stats.total_words,
+            max_words: Some(stats.total_words),
+            ..Default::default()
+        };
+        assert!(constraints.is_satisfied_by(&stats));
+        let constraints = BoardConstraints {
+            min_long_words: stats.long_words + 1,
+            ..Default::default()
+        };
+        assert!(!constraints.is_satisfied_by(&stats));
+        let constraints = BoardConstraints {
+            difficulty: Some(Difficulty::of_score(stats.difficulty)),
+            ..Default::default()
+        };
+        assert!(constraints.is_satisfied_by(&stats));
+    }
+}
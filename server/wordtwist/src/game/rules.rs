@@ -0,0 +1,86 @@
+use serde::{Deserialize, Serialize};
+
+use crate::words::{Blocklist, Dictionary};
+
+/// Which of the dictionary's words count in a game, and how they may be traced through the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct GameRules {
+    /// The fewest letters a word can have.
+    pub min_word_length: usize,
+    /// Leaves out words made by adding an "s" to another word, like "cats".
+    pub exclude_plurals: bool,
+    /// Leaves out words the dictionary only lists capitalized, like names and places.
+    pub exclude_proper_nouns: bool,
+    /// Leaves out words on the dictionary's list of offensive words.
+    pub exclude_offensive: bool,
+    /// Whether a word may use the same square more than once, though never twice in a row.
+    pub reuse_cells: bool,
+}
+
+impl Default for GameRules {
+    fn default() -> Self {
+        GameRules {
+            min_word_length: 3,
+            exclude_plurals: false,
+            exclude_proper_nouns: false,
+            exclude_offensive: false,
+            reuse_cells: false,
+        }
+    }
+}
+
+impl GameRules {
+    /// The rules games stored before rules could be chosen were played by, where every word of the
+    /// dictionary counted.
+    pub(crate) fn legacy() -> Self {
+        GameRules {
+            min_word_length: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Returns whether `word`, from `dictionary`, counts under these rules.
+    ///
+    /// Words on the [`Blocklist`] never count, whatever the rules.
+    pub fn allows(&self, word: &str, dictionary: &Dictionary) -> bool {
+        let excluded = (self.exclude_plurals && dictionary.is_plural(word))
+            || (self.exclude_proper_nouns && dictionary.is_proper_noun(word))
+            || (self.exclude_offensive && dictionary.is_offensive(word));
+        word.chars().count() >= self.min_word_length
+            && !excluded
+            && !Blocklist::global().contains(word)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn word_filtering() {
+        let dictionary = Dictionary::new("test", ["at", "cat", "cats", "Paris", "drat"])
+            .with_offensive(["drat"]);
+        let rules = GameRules::default();
+        assert!(!rules.allows("at", &dictionary));
+        assert!(GameRules::legacy().allows("at", &dictionary));
+        for word in ["cat", "cats", "paris", "drat"] {
+            assert!(rules.allows(word, &dictionary));
+        }
+
+        let rules = GameRules {
+            exclude_plurals: true,
+            exclude_proper_nouns: true,
+            exclude_offensive: true,
+            ..Default::default()
+        };
+        assert!(rules.allows("cat", &dictionary));
+        assert!(!rules.allows("cats", &dictionary));
+        assert!(!rules.allows("paris", &dictionary));
+        assert!(!rules.allows("drat", &dictionary));
+
+        let dictionary = Dictionary::new("test", ["fag", "fig"]);
+        assert!(!GameRules::legacy().allows("fag", &dictionary));
+        assert!(GameRules::legacy().allows("fig", &dictionary));
+    }
+}
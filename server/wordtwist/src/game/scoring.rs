@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+
+use super::Game;
+
+/// A rule for how many points a found word is worth.
+pub trait ScoringRule {
+    /// Returns the points awarded for finding `word` in `game`.
+    fn score_word(&self, game: &Game, word: &str) -> usize;
+}
+
+/// Awards `2^n` points for a word of length `n`.
+pub struct Exponential;
+
+impl ScoringRule for Exponential {
+    fn score_word(&self, _: &Game, word: &str) -> usize {
+        2_usize.pow(word.chars().count() as u32)
+    }
+}
+
+/// Awards points by word length, following the classic Boggle table.
+pub struct ClassicBoggle;
+
+impl ScoringRule for ClassicBoggle {
+    fn score_word(&self, _: &Game, word: &str) -> usize {
+        match word.chars().count() {
+            0..=2 => 0,
+            3 | 4 => 1,
+            5 => 2,
+            6 => 3,
+            7 => 5,
+            _ => 11,
+        }
+    }
+}
+
+/// Awards the sum of each letter's Scrabble tile value.
+pub struct LetterValue;
+
+impl LetterValue {
+    pub(crate) fn letter_value(letter: char) -> usize {
+        match letter {
+            'd' | 'g' => 2,
+            'b' | 'c' | 'm' | 'p' => 3,
+            'f' | 'h' | 'v' | 'w' | 'y' => 4,
+            'k' => 5,
+            'j' | 'x' => 8,
+            'q' | 'z' => 10,
+            _ => 1,
+        }
+    }
+}
+
+impl ScoringRule for LetterValue {
+    /// Blank squares are worth nothing, whichever letter they stand for.
+    fn score_word(&self, game: &Game, word: &str) -> usize {
+        let blanks: usize = game
+            .blanks(word)
+            .into_iter()
+            .map(|(_, letter)| LetterValue::letter_value(letter))
+            .sum();
+        word.chars().map(LetterValue::letter_value).sum::<usize>() - blanks
+    }
+}
+
+/// Scores words with `base`, multiplying the score of the game's hidden target word by `multiplier`.
+pub struct TargetWordBonus<R> {
+    pub base: R,
+    pub multiplier: usize,
+}
+
+impl<R: ScoringRule> ScoringRule for TargetWordBonus<R> {
+    fn score_word(&self, game: &Game, word: &str) -> usize {
+        let score = self.base.score_word(game, word);
+        if game.target_word() == Some(word) {
+            score * self.multiplier
+        } else {
+            score
+        }
+    }
+}
+
+/// The built-in scoring rules a game can be played with.
+///
+/// This is stored with each game, so a game is always scored by the rule it was created with.
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum Scoring {
+    /// See [`Exponential`].
+    #[default]
+    Exponential,
+    /// See [`ClassicBoggle`].
+    Classic,
+    /// See [`LetterValue`].
+    LetterValue,
+    /// [`Exponential`], with the hidden target word worth triple.
+    TargetBonus,
+}
+
+impl ScoringRule for Scoring {
+    fn score_word(&self, game: &Game, word: &str) -> usize {
+        match self {
+            Scoring::Exponential => Exponential.score_word(game, word),
+            Scoring::Classic => ClassicBoggle.score_word(game, word),
+            Scoring::LetterValue => LetterValue.score_word(game, word),
+            Scoring::TargetBonus => TargetWordBonus {
+                base: Exponential,
+                multiplier: 3,
+            }
+            .score_word(game, word),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_rules() {
+        let game = Game::from_target_word(4, "question").unwrap();
+
+        assert_eq!(Exponential.score_word(&game, "cat"), 8);
+        assert_eq!(ClassicBoggle.score_word(&game, "cat"), 1);
+        assert_eq!(ClassicBoggle.score_word(&game, "catalog"), 5);
+        assert_eq!(ClassicBoggle.score_word(&game, "catalogue"), 11);
+        assert_eq!(LetterValue.score_word(&game, "quiz"), 22);
+
+        assert_eq!(Scoring::TargetBonus.score_word(&game, "question"), 3 * 256);
+        assert_eq!(Scoring::TargetBonus.score_word(&game, "cat"), 8);
+    }
+}
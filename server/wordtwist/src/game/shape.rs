@@ -0,0 +1,226 @@
+use serde::{Deserialize, Serialize};
+
+use crate::words::{Grid, Topology};
+
+/// Which squares of a board's bounding rectangle are part of the board.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Mask {
+    /// Every square is used.
+    #[default]
+    Full,
+    /// Only squares within a diamond touching the middle of each edge are used.
+    Diamond,
+    /// Only squares in the middle third of the rows or of the columns are used.
+    Cross,
+    /// Only squares within a hexagon are used, for boards with a
+    /// [`Topology::Hex`] topology.
+    Hexagon,
+    /// Every square except the listed `(y, x)` squares is used.
+    Holes(Vec<(usize, usize)>),
+}
+
+impl Mask {
+    /// Parses a [`Mask::Holes`] mask from the squares to leave out, written as `y,x` pairs separated
+    /// by semicolons, like `0,0;2,3`, so they can be given in a query string.
+    pub fn parse_holes(s: &str) -> Option<Mask> {
+        s.split(';')
+            .map(|square| {
+                let (y, x) = square.split_once(',')?;
+                Some((y.trim().parse().ok()?, x.trim().parse().ok()?))
+            })
+            .collect::<Option<_>>()
+            .map(Mask::Holes)
+    }
+}
+
+/// The shape of a board: `rows` x `cols` squares, with some masked out.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Shape {
+    pub rows: usize,
+    pub cols: usize,
+    #[serde(default)]
+    pub mask: Mask,
+}
+
+impl Shape {
+    /// Returns the shape of a full `size` x `size` board.
+    pub fn square(size: usize) -> Self {
+        Shape {
+            rows: size,
+            cols: size,
+            mask: Mask::Full,
+        }
+    }
+
+    /// Returns the shape of `grid`, treating its empty tiles as holes.
+    pub fn of_grid(grid: &Grid) -> Self {
+        let holes: Vec<_> = grid
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(_, tile)| tile.is_empty())
+                    .map(move |(x, _)| (y, x))
+            })
+            .collect();
+        Shape {
+            rows: grid.len(),
+            cols: grid.first().map_or(0, Vec::len),
+            mask: if holes.is_empty() {
+                Mask::Full
+            } else {
+                Mask::Holes(holes)
+            },
+        }
+    }
+
+    /// Returns whether the `(y, x)` square is part of the board.
+    pub fn contains(&self, (y, x): (usize, usize)) -> bool {
+        if y >= self.rows || x >= self.cols {
+            return false;
+        }
+        match &self.mask {
+            Mask::Full => true,
+            Mask::Diamond => {
+                // distances from the centre, doubled so they stay whole on even sides
+                let dy = (2 * y).abs_diff(self.rows - 1);
+                let dx = (2 * x).abs_diff(self.cols - 1);
+                dy * self.cols + dx * self.rows <= self.rows * self.cols
+            }
+            Mask::Cross => {
+                let middle = |n: usize, i: usize| (n / 3..n - n / 3).contains(&i);
+                middle(self.rows, y) || middle(self.cols, x)
+            }
+            Mask::Hexagon => {
+                // in axial coordinates, trimming the two far corners of the rhombus leaves a hexagon
+                let corner = (self.rows.min(self.cols) - 1) / 2;
+                (corner..=self.rows + self.cols - 2 - corner).contains(&(y + x))
+            }
+            Mask::Holes(holes) => !holes.contains(&(y, x)),
+        }
+    }
+
+    /// Returns every square that is part of the board, row by row.
+    pub fn cells(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..self.rows)
+            .flat_map(move |y| (0..self.cols).map(move |x| (y, x)))
+            .filter(|&square| self.contains(square))
+    }
+
+    /// Returns how many squares are part of the board.
+    pub fn len(&self) -> usize {
+        self.cells().count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns how many squares the largest region of the board has, a region being squares
+    /// connected to each other under `topology`.
+    pub fn largest_region(&self, topology: Topology) -> usize {
+        let mut seen = vec![vec![false; self.cols]; self.rows];
+        let mut largest = 0;
+        for start in self.cells() {
+            if seen[start.0][start.1] {
+                continue;
+            }
+            seen[start.0][start.1] = true;
+            let mut unvisited = vec![start];
+            let mut size = 0;
+            while let Some(square) = unvisited.pop() {
+                size += 1;
+                for (y, x) in topology.neighbours(square, self.rows, self.cols) {
+                    if self.contains((y, x)) && !seen[y][x] {
+                        seen[y][x] = true;
+                        unvisited.push((y, x));
+                    }
+                }
+            }
+            largest = largest.max(size);
+        }
+        largest
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(shape: &Shape) -> Vec<String> {
+        (0..shape.rows)
+            .map(|y| {
+                (0..shape.cols)
+                    .map(|x| if shape.contains((y, x)) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn masks() {
+        let shape = |rows, cols, mask| Shape { rows, cols, mask };
+
+        assert_eq!(render(&shape(2, 3, Mask::Full)), ["###", "###"]);
+        assert_eq!(
+            render(&shape(5, 5, Mask::Diamond)),
+            ["..#..", ".###.", "#####", ".###.", "..#.."]
+        );
+        assert_eq!(
+            render(&shape(4, 4, Mask::Diamond)),
+            [".##.", "####", "####", ".##."]
+        );
+        assert_eq!(render(&shape(3, 3, Mask::Cross)), [".#.", "###", ".#."]);
+        assert_eq!(
+            render(&shape(3, 4, Mask::Holes(vec![(0, 0), (2, 3)]))),
+            [".###", "####", "###."]
+        );
+        assert_eq!(
+            render(&shape(5, 5, Mask::Hexagon)),
+            ["..###", ".####", "#####", "####.", "###.."]
+        );
+        assert_eq!(shape(5, 5, Mask::Diamond).len(), 13);
+        assert_eq!(shape(5, 5, Mask::Hexagon).len(), 19);
+    }
+
+    #[test]
+    fn largest_region() {
+        assert_eq!(Shape::square(4).largest_region(Topology::Bounded), 16);
+        // every other column is a hole, leaving columns of three squares
+        let columns = Shape {
+            rows: 3,
+            cols: 7,
+            mask: Mask::parse_holes("0,1;0,3;0,5;1,1;1,3;1,5;2,1;2,3;2,5").unwrap(),
+        };
+        assert_eq!(columns.largest_region(Topology::Bounded), 3);
+        // the first and last columns meet around the edge of a torus
+        assert_eq!(columns.largest_region(Topology::Torus), 6);
+    }
+
+    #[test]
+    fn holes_from_str() {
+        assert_eq!(
+            Mask::parse_holes("0,0; 2,3"),
+            Some(Mask::Holes(vec![(0, 0), (2, 3)]))
+        );
+        assert_eq!(Mask::parse_holes(""), None);
+        assert_eq!(Mask::parse_holes("0,0;2"), None);
+        assert_eq!(Mask::parse_holes("0,-1"), None);
+    }
+
+    #[test]
+    fn shape_of_grid() {
+        let grid = vec![
+            vec!["a".to_string(), String::new()],
+            vec!["b".to_string(), "c".to_string()],
+            vec!["d".to_string(), "e".to_string()],
+        ];
+        let shape = Shape::of_grid(&grid);
+        assert_eq!((shape.rows, shape.cols), (3, 2));
+        assert_eq!(shape.mask, Mask::Holes(vec![(0, 1)]));
+        assert_eq!(Shape::of_grid(&grid[1..].to_vec()), Shape::square(2));
+    }
+}
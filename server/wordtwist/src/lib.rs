@@ -1,2 +1,3 @@
 pub mod game;
+mod trie;
 pub mod words;
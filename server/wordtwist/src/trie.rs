@@ -0,0 +1,97 @@
+/// A prefix tree over a word list, used to search a grid for every word at once.
+#[derive(Debug)]
+pub(crate) struct Trie {
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug, Default)]
+struct Node {
+    /// Child node indices, keyed by the next character of the word.
+    children: Vec<(char, usize)>,
+    /// Whether the path from the root to this node spells a complete word.
+    terminal: bool,
+}
+
+/// Index of the root node, which represents the empty prefix.
+pub(crate) const ROOT: usize = 0;
+
+impl Trie {
+    /// Builds a `Trie` containing every word in `words`.
+    pub(crate) fn from_words<S: AsRef<str>>(words: &[S]) -> Self {
+        let mut trie = Trie {
+            nodes: vec![Node::default()],
+        };
+        for word in words {
+            trie.insert(word.as_ref());
+        }
+        trie
+    }
+
+    fn insert(&mut self, word: &str) {
+        let mut node = ROOT;
+        for char in word.chars() {
+            node = match self.child(node, char) {
+                Some(child) => child,
+                None => {
+                    self.nodes.push(Node::default());
+                    let child = self.nodes.len() - 1;
+                    self.nodes[node].children.push((char, child));
+                    child
+                }
+            };
+        }
+        self.nodes[node].terminal = true;
+    }
+
+    /// Returns the node reached by following `char` from `node`, if any word continues that way.
+    pub(crate) fn child(&self, node: usize, char: char) -> Option<usize> {
+        self.nodes[node]
+            .children
+            .iter()
+            .find(|(c, _)| *c == char)
+            .map(|(_, child)| *child)
+    }
+
+    /// Returns every `(char, child)` pair continuing a word from `node`.
+    pub(crate) fn children(&self, node: usize) -> &[(char, usize)] {
+        &self.nodes[node].children
+    }
+
+    /// Returns whether `word` is one of the words in the trie.
+    pub(crate) fn contains(&self, word: &str) -> bool {
+        word.chars()
+            .try_fold(ROOT, |node, char| self.child(node, char))
+            .is_some_and(|node| self.is_terminal(node))
+    }
+
+    /// Returns whether `node` ends a complete word.
+    pub(crate) fn is_terminal(&self, node: usize) -> bool {
+        self.nodes[node].terminal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lookup(trie: &Trie, word: &str) -> Option<usize> {
+        word.chars()
+            .try_fold(ROOT, |node, char| trie.child(node, char))
+    }
+
+    #[test]
+    fn prefixes_and_words() {
+        let trie = Trie::from_words(&["bar", "bark", "foo"]);
+
+        assert!(trie.is_terminal(lookup(&trie, "bar").unwrap()));
+        assert!(trie.is_terminal(lookup(&trie, "bark").unwrap()));
+        assert!(!trie.is_terminal(lookup(&trie, "ba").unwrap()));
+        assert!(lookup(&trie, "baz").is_none());
+        assert!(lookup(&trie, "fooo").is_none());
+        assert_eq!(trie.children(lookup(&trie, "bar").unwrap()).len(), 1);
+
+        assert!(trie.contains("bar"));
+        assert!(!trie.contains("ba"));
+        assert!(!trie.contains("barks"));
+    }
+}
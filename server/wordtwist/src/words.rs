@@ -1,81 +1,205 @@
-use std::sync::OnceLock;
+#[cfg(any(test, feature = "bench"))]
+use std::collections::HashMap;
+use std::{collections::BTreeMap, ops::Range};
 
 use rand::{
     seq::{IteratorRandom, SliceRandom},
-    thread_rng,
+    Rng,
 };
+use serde::{Deserialize, Serialize};
 
-static WORDS: OnceLock<Vec<String>> = OnceLock::new();
+use crate::{
+    game::GameRules,
+    trie::{Trie, ROOT},
+};
+
+mod blocklist;
+mod definitions;
+mod dictionary;
+mod distribution;
+mod topology;
+
+pub use blocklist::Blocklist;
+pub use definitions::Definitions;
+pub use dictionary::{Dictionary, DEFAULT_DICTIONARY};
+pub use distribution::{LetterDistribution, LetterWeights, WeightsError};
+pub use topology::Topology;
+
+/// A game board, as rows of tiles. Most tiles are a single letter, but some alphabets have tiles
+/// of several letters, like "qu". Squares masked out of irregular boards hold an empty tile, and
+/// blank squares hold a [`WILDCARD`] tile.
+pub type Grid = Vec<Vec<String>>;
+
+/// The tile of a blank square, which stands for any single letter.
+pub const WILDCARD: &str = "*";
+
+/// The most blank squares a word can cross. Each blank multiplies the paths the solver follows by
+/// the size of the alphabet, so boards full of blanks would otherwise take minutes to solve.
+pub const MAX_BLANKS_PER_WORD: usize = 2;
+
+/// A sequence of `(y, x)` squares in a grid.
+pub type Path = Vec<(usize, usize)>;
+
+/// Every word that can be found in a grid, each mapped to a path of squares that spells it.
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct Solution(BTreeMap<String, Path>);
+
+impl Solution {
+    /// Returns the words of this solution, in sorted order.
+    pub fn words(&self) -> impl Iterator<Item = &String> {
+        self.0.keys()
+    }
 
-/// Initializes the `WORDS` vector on first call, returns the reference on subsequent calls
-fn get_words() -> &'static [String] {
-    WORDS.get_or_init(|| {
-        let text = include_str!("../words.txt");
-        text.lines().map(|w| w.to_lowercase()).collect()
-    })
+    /// Returns a path spelling `word`, if it is part of this solution.
+    pub fn path(&self, word: &str) -> Option<&Path> {
+        self.0.get(word)
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.0.contains_key(word)
+    }
+
+    /// Returns the letter each [`WILDCARD`] square on the path of `word` through `grid` stands
+    /// for, if `word` is part of this solution.
+    pub fn blanks(&self, word: &str, grid: &Grid) -> Option<Vec<((usize, usize), char)>> {
+        let mut rest = word;
+        let mut blanks = Vec::new();
+        for &(y, x) in self.path(word)? {
+            let tile = &grid[y][x];
+            if tile == WILDCARD {
+                blanks.push(((y, x), rest.chars().next()?));
+            }
+            rest = strip_tile(rest, tile)?;
+        }
+        Some(blanks)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns a new `Solution` containing only the words for which `keep` returns true.
+    pub fn filter(&self, mut keep: impl FnMut(&str) -> bool) -> Solution {
+        Solution(
+            self.0
+                .iter()
+                .filter(|(word, _)| keep(word))
+                .map(|(word, path)| (word.clone(), path.clone()))
+                .collect(),
+        )
+    }
 }
 
-/// Provided a size `n`, returns a vector of all words of that size, using the module's lazily loaded wordlist.
-fn get_all_n_length_words(n: usize) -> Vec<String> {
+/// Provided a size `n`, returns a vector of all words of that size in `dictionary` for which `keep`
+/// returns true.
+fn get_all_n_length_words(
+    dictionary: &Dictionary,
+    n: usize,
+    keep: impl Fn(&str) -> bool,
+) -> Vec<String> {
     let mut output = Vec::new();
 
-    for word in get_words().iter() {
-        if word.chars().count() == n {
+    for word in dictionary.words().iter() {
+        if word.chars().count() == n && keep(word) {
             output.push(word.to_string())
         }
     }
     output
 }
 
-/// Randomly selects from `words`.
-pub fn get_random_word(words: &[String]) -> Option<&String> {
-    words.choose(&mut thread_rng())
+/// Randomly selects from `words`, using `rng`.
+pub fn get_random_word<'a, R: Rng + ?Sized>(
+    words: &'a [String],
+    rng: &mut R,
+) -> Option<&'a String> {
+    words.choose(rng)
 }
 
-/// Generates a vowel-weighted random letter.
+/// Randomly selects a word from `dictionary` whose length is in `lengths`, and for which `keep`
+/// returns true, using `rng`.
 ///
-/// Vowels are twice as likely to be selected as consonants.
-pub fn get_random_letter() -> char {
-    ('a'..='z')
-        .chain(['a', 'e', 'i', 'o', 'u'])
-        .choose(&mut thread_rng())
-        .unwrap()
+/// Each length with at least one word is equally likely to be chosen, regardless of how many words
+/// have that length. Returns `None` if no word has a length in `lengths`.
+pub fn get_random_word_with_length_in<R: Rng + ?Sized>(
+    dictionary: &Dictionary,
+    lengths: Range<usize>,
+    keep: impl Fn(&str) -> bool,
+    rng: &mut R,
+) -> Option<String> {
+    let words_by_length: Vec<Vec<String>> = lengths
+        .map(|n| get_all_n_length_words(dictionary, n, &keep))
+        .filter(|words| !words.is_empty())
+        .collect();
+    let words = words_by_length.choose(rng)?;
+    get_random_word(words, rng).cloned()
+}
+
+/// Generates a vowel-weighted random tile from `dictionary`'s alphabet, using `rng`.
+///
+/// Vowels are twice as likely to be selected as other tiles.
+pub fn get_random_tile<R: Rng + ?Sized>(dictionary: &Dictionary, rng: &mut R) -> String {
+    let alphabet = dictionary.alphabet();
+    alphabet
+        .iter()
+        .chain(
+            alphabet
+                .iter()
+                .filter(|t| ["a", "e", "i", "o", "u"].contains(&t.as_str())),
+        )
+        .choose(rng)
+        .expect("dictionary has an empty alphabet")
+        .clone()
 }
 
-/// Generates a random word of length `n` from the module's lazily loaded wordlist.
-pub fn get_random_n_length_word(n: usize) -> String {
-    get_random_word(&get_all_n_length_words(n))
+/// Generates a random word of length `n` from `dictionary`, using `rng`.
+pub fn get_random_n_length_word<R: Rng + ?Sized>(
+    dictionary: &Dictionary,
+    n: usize,
+    rng: &mut R,
+) -> String {
+    get_random_word(&get_all_n_length_words(dictionary, n, |_| true), rng)
         .expect("Requested word of nonexistant size!")
         .clone()
 }
 
-/// Counts the number of each character of `word`, returning an array of counts
-/// (where the 0th index is 'a')
-///
-/// Panics if `word` doesn't consist of lowercase regular Latin letters.
-fn count_chars(word: &str) -> [usize; 26] {
-    let mut counts = [0; 26];
+/// Counts the number of each character of `word`, returning a map of counts.
+#[cfg(any(test, feature = "bench"))]
+fn count_chars(word: &str) -> HashMap<char, usize> {
+    let mut counts = HashMap::new();
     for char in word.chars() {
-        counts[(char as u8 - b'a') as usize] += 1;
+        *counts.entry(char).or_insert(0) += 1;
     }
     counts
 }
 
-/// Given `words` and `characters`, returns a new vector of only words solely comprised of
-/// those characters in the provided count.
+/// Given a `dictionary` and `characters`, returns a new vector of only words solely comprised of
+/// those characters in the provided count, plus up to `blanks` other characters.
 ///
 /// This is to trim down the possible words to search for in grid permutations to a managable amount.
-fn filter_words_by_character(characters: &str) -> Vec<String> {
+#[cfg(any(test, feature = "bench"))]
+fn filter_words_by_character(
+    dictionary: &Dictionary,
+    characters: &str,
+    blanks: usize,
+) -> Vec<String> {
     let char_count = count_chars(characters);
-    get_words()
+    dictionary
+        .words()
         .iter()
         .filter_map(|w| {
             let word_count = count_chars(w);
-            if word_count
+            let missing: usize = word_count
                 .iter()
-                .enumerate()
-                .all(|(idx, count)| char_count[idx] >= *count)
-            {
+                .map(|(char, count)| {
+                    count.saturating_sub(char_count.get(char).copied().unwrap_or(0))
+                })
+                .sum();
+            if missing <= blanks {
                 return Some(w.to_string());
             }
             None
@@ -83,110 +207,214 @@ fn filter_words_by_character(characters: &str) -> Vec<String> {
         .collect()
 }
 
-/// Given a game `&grid`, returns a vector of all the words that can be found inside that grid.
+/// Returns the rest of `word` after the letters of `tile`, if it starts with them.
 ///
-/// Panics if `grid` contains non ASCII lowercase characters
-pub fn generate_wordlist_from_game(grid: &Vec<Vec<char>>) -> Vec<String> {
+/// A [`WILDCARD`] tile matches any single letter.
+fn strip_tile<'a>(word: &'a str, tile: &str) -> Option<&'a str> {
+    if tile == WILDCARD {
+        let mut chars = word.chars();
+        chars.next().map(|_| chars.as_str())
+    } else {
+        word.strip_prefix(tile)
+    }
+}
+
+/// Returns whether the `tiles` in order spell `word`, with each [`WILDCARD`] tile standing for any
+/// single letter.
+pub(crate) fn tiles_spell<S: AsRef<str>>(tiles: &[S], word: &str) -> bool {
+    tiles
+        .iter()
+        .try_fold(word, |rest, tile| strip_tile(rest, tile.as_ref()))
+        .is_some_and(str::is_empty)
+}
+
+/// Given a game `&grid`, whose squares connect as described by `topology`, returns every word of
+/// `dictionary` allowed by `rules` that can be found inside that grid, along with the path of
+/// squares that spells it.
+///
+/// Walks the grid once from every square, pruning any path that isn't a prefix of a word. Words
+/// crossing more than [`MAX_BLANKS_PER_WORD`] blank squares aren't found.
+pub fn solve_game(
+    grid: &Grid,
+    topology: Topology,
+    rules: &GameRules,
+    dictionary: &Dictionary,
+) -> Solution {
+    /// The state of a walk over the grid, looking for words.
+    struct Search<'a> {
+        grid: &'a Grid,
+        topology: Topology,
+        rules: &'a GameRules,
+        dictionary: &'a Dictionary,
+        trie: &'a Trie,
+        /// The word spelled by `path`.
+        word: String,
+        path: Path,
+        /// How many blank squares `path` crosses.
+        blanks: usize,
+        found: BTreeMap<String, Path>,
+    }
+
+    impl Search<'_> {
+        /// Recursive helper function to extend the path onto `(y, x)`, from where it has reached
+        /// `node` in the trie.
+        fn search_from_square(&mut self, node: usize, (y, x): (usize, usize)) {
+            let (grid, trie) = (self.grid, self.trie);
+            let tile = &grid[y][x];
+            if tile.is_empty() {
+                return;
+            }
+            if tile == WILDCARD {
+                if self.blanks == MAX_BLANKS_PER_WORD {
+                    return;
+                }
+                // try the blank as every letter that continues a word from here
+                self.blanks += 1;
+                for &(letter, child) in trie.children(node) {
+                    self.enter_square(child, (y, x), letter.encode_utf8(&mut [0; 4]));
+                }
+                self.blanks -= 1;
+            } else if let Some(node) = tile
+                .chars()
+                .try_fold(node, |node, char| trie.child(node, char))
+            {
+                self.enter_square(node, (y, x), tile);
+            }
+        }
+
+        /// Adds `(y, x)`, spelling `letters`, to the path, which reaches `node` in the trie, and
+        /// searches on from there.
+        fn enter_square(&mut self, node: usize, (y, x): (usize, usize), letters: &str) {
+            self.path.push((y, x));
+            self.word.push_str(letters);
+            if self.trie.is_terminal(node)
+                && !self.found.contains_key(self.word.as_str())
+                && self.rules.allows(&self.word, self.dictionary)
+            {
+                self.found.insert(self.word.clone(), self.path.clone());
+            }
+
+            let (rows, cols) = (self.grid.len(), self.grid[y].len());
+            for next in self.topology.neighbours((y, x), rows, cols) {
+                if self.rules.reuse_cells || !self.path.contains(&next) {
+                    self.search_from_square(node, next);
+                }
+            }
+
+            self.word.truncate(self.word.len() - letters.len());
+            self.path.pop();
+        }
+    }
+
+    let mut search = Search {
+        grid,
+        topology,
+        rules,
+        dictionary,
+        trie: dictionary.trie(),
+        word: String::new(),
+        path: Vec::new(),
+        blanks: 0,
+        found: BTreeMap::new(),
+    };
+    for (y, row) in grid.iter().enumerate() {
+        for x in 0..row.len() {
+            search.search_from_square(ROOT, (y, x));
+        }
+    }
+    Solution(search.found)
+}
+
+/// Given a game `&grid`, returns a sorted vector of all the words of `dictionary` allowed by `rules`
+/// that can be found inside that grid.
+pub fn generate_wordlist_from_game(
+    grid: &Grid,
+    topology: Topology,
+    rules: &GameRules,
+    dictionary: &Dictionary,
+) -> Vec<String> {
+    solve_game(grid, topology, rules, dictionary)
+        .words()
+        .cloned()
+        .collect()
+}
+
+/// Given a game `&grid`, returns a sorted vector of all the words of `dictionary` allowed by `rules`
+/// that can be found inside that grid.
+///
+/// This is the original solver, which searches the grid separately for every word that could be
+/// spelled from its letters. It is kept to check and benchmark [`generate_wordlist_from_game`]
+/// against, so is only built for tests and with the `bench` feature.
+#[cfg(any(test, feature = "bench"))]
+pub fn generate_wordlist_by_word_search(
+    grid: &Grid,
+    topology: Topology,
+    rules: &GameRules,
+    dictionary: &Dictionary,
+) -> Vec<String> {
     /// Recursive helper function to search for the remaining `word` slice in the `grid`.
     fn search_for_word(
-        grid: &Vec<Vec<char>>,
+        grid: &Grid,
+        topology: Topology,
+        rules: &GameRules,
         word: &str,
         (y, x): (usize, usize),
         visited_squares: &mut Vec<Vec<bool>>,
     ) -> bool {
-        let grid_length = grid.len();
-        visited_squares[y][x] = true;
-
-        let next_char = match word.chars().next() {
-            None => return true,
-            Some(char) => char,
-        };
+        visited_squares[y][x] = !rules.reuse_cells;
 
-        // up
-        if y > 0
-            && grid[y - 1][x] == next_char
-            && !visited_squares[y - 1][x]
-            && search_for_word(grid, &word[1..], (y - 1, x), visited_squares)
-        {
-            return true;
-        }
-        // up left
-        if y > 0
-            && x > 0
-            && grid[y - 1][x - 1] == next_char
-            && !visited_squares[y - 1][x - 1]
-            && search_for_word(grid, &word[1..], (y - 1, x - 1), visited_squares)
-        {
-            return true;
-        }
-        // up right
-        if y > 0
-            && x < grid_length - 1
-            && grid[y - 1][x + 1] == next_char
-            && !visited_squares[y - 1][x + 1]
-            && search_for_word(grid, &word[1..], (y - 1, x + 1), visited_squares)
-        {
-            return true;
-        }
-        // down
-        if y < grid_length - 1
-            && grid[y + 1][x] == next_char
-            && !visited_squares[y + 1][x]
-            && search_for_word(grid, &word[1..], (y + 1, x), visited_squares)
-        {
+        if word.is_empty() {
             return true;
         }
-        // down left
-        if y < grid_length - 1
-            && x > 0
-            && grid[y + 1][x - 1] == next_char
-            && !visited_squares[y + 1][x - 1]
-            && search_for_word(grid, &word[1..], (y + 1, x - 1), visited_squares)
-        {
-            return true;
-        }
-        // down right
-        if y < grid_length - 1
-            && x < grid_length - 1
-            && grid[y + 1][x + 1] == next_char
-            && !visited_squares[y + 1][x + 1]
-            && search_for_word(grid, &word[1..], (y + 1, x + 1), visited_squares)
-        {
-            return true;
-        }
-        // left
-        if x > 0
-            && grid[y][x - 1] == next_char
-            && !visited_squares[y][x - 1]
-            && search_for_word(grid, &word[1..], (y, x - 1), visited_squares)
-        {
-            return true;
-        }
-        // right
-        if x < grid_length - 1
-            && grid[y][x + 1] == next_char
-            && !visited_squares[y][x + 1]
-            && search_for_word(grid, &word[1..], (y, x + 1), visited_squares)
-        {
-            return true;
+
+        for (next_y, next_x) in topology.neighbours((y, x), grid.len(), grid[y].len()) {
+            if visited_squares[next_y][next_x] {
+                continue;
+            }
+            if let Some(rest) = strip_tile(word, &grid[next_y][next_x]) {
+                if search_for_word(
+                    grid,
+                    topology,
+                    rules,
+                    rest,
+                    (next_y, next_x),
+                    visited_squares,
+                ) {
+                    return true;
+                }
+            }
         }
         visited_squares[y][x] = false;
         false
     }
 
-    let letters: String = grid.iter().flatten().copied().collect();
+    let letters: String = grid.iter().flatten().map(|t| t.as_str()).collect();
 
-    let possible_words = filter_words_by_character(&letters);
+    let blanks = grid.iter().flatten().filter(|t| *t == WILDCARD).count();
+    // squares used more than once spell more letters than the grid holds
+    let possible_words = if rules.reuse_cells {
+        dictionary.words().to_vec()
+    } else {
+        filter_words_by_character(dictionary, &letters, blanks)
+    };
     let mut wordlist: Vec<String> = Vec::new();
 
     'words: for word in possible_words {
-        let first_char = word.chars().next().unwrap();
-
+        if !rules.allows(&word, dictionary) {
+            continue;
+        }
         for (y, row) in grid.iter().enumerate() {
-            for (x, grid_character) in row.iter().enumerate() {
-                if *grid_character == first_char {
-                    let mut visited_squares = vec![vec![false; grid.len()]; grid[0].len()];
-                    if search_for_word(grid, &word[1..], (y, x), &mut visited_squares) {
+            for (x, tile) in row.iter().enumerate() {
+                if tile.is_empty() {
+                    continue;
+                }
+                if let Some(rest) = strip_tile(&word, tile) {
+                    // masked out squares can't be visited, so start with them marked as visited
+                    let mut visited_squares: Vec<Vec<bool>> = grid
+                        .iter()
+                        .map(|row| row.iter().map(String::is_empty).collect())
+                        .collect();
+                    if search_for_word(grid, topology, rules, rest, (y, x), &mut visited_squares) {
                         wordlist.push(word);
                         continue 'words;
                     }
@@ -194,20 +422,33 @@ pub fn generate_wordlist_from_game(grid: &Vec<Vec<char>>) -> Vec<String> {
             }
         }
     }
+    wordlist.sort();
+    wordlist.dedup();
     wordlist
 }
 
+/// Builds a grid of single letter tiles from `rows` of letters.
+#[cfg(test)]
+pub(crate) fn letter_grid(rows: &[&str]) -> Grid {
+    rows.iter()
+        .map(|r| r.chars().map(String::from).collect())
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
+    use rand::thread_rng;
+
     use super::*;
+    use crate::game::{Game, GameConfig, Mask, Shape};
 
     #[test]
     fn find_lengthed_words() {
-        let twelve_length_words = get_all_n_length_words(12);
+        let twelve_length_words = get_all_n_length_words(Dictionary::embedded(), 12, |_| true);
         for _word in twelve_length_words.iter() {
             // println!("{}", word)
         }
-        let fifteen_length_words = get_all_n_length_words(15);
+        let fifteen_length_words = get_all_n_length_words(Dictionary::embedded(), 15, |_| true);
         for _word in fifteen_length_words.iter() {
             // println!("{}", word)
         }
@@ -215,41 +456,325 @@ mod tests {
 
     #[test]
     fn get_random_n_length_word() {
-        let twelve_length_words = get_all_n_length_words(12);
-        let random_twelve_length_word = get_random_word(&twelve_length_words);
+        let twelve_length_words = get_all_n_length_words(Dictionary::embedded(), 12, |_| true);
+        let random_twelve_length_word = get_random_word(&twelve_length_words, &mut thread_rng());
 
         assert_eq!(random_twelve_length_word.unwrap().chars().count(), 12);
     }
 
     #[test]
-    fn test_random_letter() {
-        get_random_letter();
+    fn random_word_with_length_in() {
+        let dictionary = Dictionary::new("test", ["a", "bb", "ccc", "dddd"]);
+        let mut rng = thread_rng();
+        for _ in 0..20 {
+            let word =
+                get_random_word_with_length_in(&dictionary, 2..4, |_| true, &mut rng).unwrap();
+            assert!(word == "bb" || word == "ccc");
+        }
+        assert_eq!(
+            get_random_word_with_length_in(&dictionary, 5..10, |_| true, &mut rng),
+            None
+        );
+    }
+
+    #[test]
+    fn test_random_tile() {
+        let tile = get_random_tile(Dictionary::embedded(), &mut thread_rng());
+        assert!(Dictionary::embedded().alphabet().contains(&tile));
     }
 
     #[test]
     fn test_count_chars() {
         let input = "boot";
         let output = count_chars(input);
-        assert_eq!(output[1], 1); // b
-        assert_eq!(output[14], 2); // o
-        assert_eq!(output[0], 0);
+        assert_eq!(output[&'b'], 1);
+        assert_eq!(output[&'o'], 2);
+        assert_eq!(output.get(&'a'), None);
     }
 
     #[test]
     fn test_filter_words_by_character() {
-        let words = filter_words_by_character("bos");
+        let words = filter_words_by_character(Dictionary::embedded(), "bos", 0);
         println!("{:?}", words);
         assert!(words.contains(&"sob".to_string()));
         assert!(!words.contains(&"boss".to_string()));
+
+        let words = filter_words_by_character(Dictionary::embedded(), "bos", 1);
+        assert!(words.contains(&"boss".to_string()));
+        assert!(words.contains(&"bogs".to_string()));
+        assert!(!words.contains(&"bogus".to_string()));
+    }
+
+    #[test]
+    fn trie_search_matches_word_search() {
+        for size in 3..=7 {
+            let game = Game::from_seed(size, size as u64);
+            assert_eq!(
+                generate_wordlist_from_game(
+                    game.grid(),
+                    Topology::Bounded,
+                    &GameRules::default(),
+                    Dictionary::embedded()
+                ),
+                generate_wordlist_by_word_search(
+                    game.grid(),
+                    Topology::Bounded,
+                    &GameRules::default(),
+                    Dictionary::embedded()
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn trie_search_matches_word_search_on_shaped_boards() {
+        for mask in [Mask::Full, Mask::Diamond, Mask::Cross] {
+            let shape = Shape {
+                rows: 4,
+                cols: 6,
+                mask,
+            };
+            let config = GameConfig {
+                shape,
+                ..GameConfig::new(4)
+            };
+            let game = Game::generate_from_seed(&config, 3);
+            assert_eq!(
+                generate_wordlist_from_game(
+                    game.grid(),
+                    Topology::Bounded,
+                    &GameRules::default(),
+                    Dictionary::embedded()
+                ),
+                generate_wordlist_by_word_search(
+                    game.grid(),
+                    Topology::Bounded,
+                    &GameRules::default(),
+                    Dictionary::embedded()
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn trie_search_matches_word_search_across_topologies() {
+        for topology in [Topology::Torus, Topology::Hex] {
+            let config = GameConfig {
+                topology,
+                ..GameConfig::new(5)
+            };
+            let game = Game::generate_from_seed(&config, 9);
+            assert_eq!(
+                generate_wordlist_from_game(
+                    game.grid(),
+                    topology,
+                    &GameRules::default(),
+                    Dictionary::embedded()
+                ),
+                generate_wordlist_by_word_search(
+                    game.grid(),
+                    topology,
+                    &GameRules::default(),
+                    Dictionary::embedded()
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn trie_search_matches_word_search_with_wildcards() {
+        let mut game = Game::generate_from_seed(&GameConfig::new(4), 6);
+        let mut grid = game.grid().clone();
+        grid[1][1] = WILDCARD.to_string();
+        grid[2][3] = WILDCARD.to_string();
+        let words = generate_wordlist_from_game(
+            &grid,
+            Topology::Bounded,
+            &GameRules::default(),
+            Dictionary::embedded(),
+        );
+        assert_eq!(
+            words,
+            generate_wordlist_by_word_search(
+                &grid,
+                Topology::Bounded,
+                &GameRules::default(),
+                Dictionary::embedded()
+            )
+        );
+        assert!(words.len() > game.valid_words().len());
+        game = Game::generate_from_seed(&GameConfig::new(4), 6);
+        assert!(game.valid_words().iter().all(|w| words.contains(w)));
+    }
+
+    #[test]
+    fn blanks_are_recorded() {
+        let grid = letter_grid(&["c*t", "xxx"]);
+        let solution = solve_game(
+            &grid,
+            Topology::Bounded,
+            &GameRules::default(),
+            Dictionary::embedded(),
+        );
+        assert_eq!(solution.blanks("cut", &grid), Some(vec![((0, 1), 'u')]));
+        assert_eq!(solution.blanks("cat", &grid), Some(vec![((0, 1), 'a')]));
+        assert_eq!(solution.blanks("dog", &grid), None);
+    }
+
+    #[test]
+    fn blanks_per_word_are_limited() {
+        let rules = GameRules {
+            min_word_length: 2,
+            ..GameRules::default()
+        };
+        // with every square blank, only words as short as the limit can be found
+        let grid = letter_grid(&["***", "***", "***"]);
+        let solution = solve_game(&grid, Topology::Bounded, &rules, Dictionary::embedded());
+        assert!(solution.contains("at"));
+        assert!(solution
+            .words()
+            .all(|w| w.chars().count() <= MAX_BLANKS_PER_WORD));
+
+        let grid = letter_grid(&["c**", "xx*"]);
+        let solution = solve_game(&grid, Topology::Bounded, &rules, Dictionary::embedded());
+        assert!(solution.contains("cat"));
+        assert!(!solution.contains("cart"));
+    }
+
+    #[test]
+    fn solver_follows_rules() {
+        let dictionary = Dictionary::new("test", ["at", "tat", "tatt", "tats", "Tas"]);
+        let grid = letter_grid(&["ta", "xs"]);
+        let solve = |rules: &GameRules| {
+            let words = generate_wordlist_from_game(&grid, Topology::Bounded, rules, &dictionary);
+            assert_eq!(
+                words,
+                generate_wordlist_by_word_search(&grid, Topology::Bounded, rules, &dictionary)
+            );
+            words
+        };
+
+        assert_eq!(solve(&GameRules::legacy()), ["at", "tas"]);
+        assert_eq!(solve(&GameRules::default()), ["tas"]);
+        assert_eq!(
+            solve(&GameRules {
+                reuse_cells: true,
+                ..Default::default()
+            }),
+            ["tas", "tat", "tats"]
+        );
+        assert!(solve(&GameRules {
+            exclude_proper_nouns: true,
+            ..Default::default()
+        })
+        .is_empty());
+    }
+
+    #[test]
+    fn holes_are_not_walked_through() {
+        let dictionary = Dictionary::new("test", ["at", "ta"]);
+        let grid = vec![vec!["a".to_string(), String::new(), "t".to_string()]];
+        assert!(
+            solve_game(&grid, Topology::Bounded, &GameRules::default(), &dictionary).is_empty()
+        );
+        assert!(generate_wordlist_by_word_search(
+            &grid,
+            Topology::Bounded,
+            &GameRules::default(),
+            &dictionary
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn wordlist_is_sorted() {
+        let grid = letter_grid(&["cat", "xrs", "xxx"]);
+        let words = generate_wordlist_from_game(
+            &grid,
+            Topology::Bounded,
+            &GameRules::default(),
+            Dictionary::embedded(),
+        );
+        assert!(words.windows(2).all(|w| w[0] < w[1]));
+        assert!(words.contains(&"cat".to_string()));
+        assert!(words.contains(&"cats".to_string()));
+        assert!(words.contains(&"car".to_string()));
+        assert!(!words.contains(&"scar".to_string()));
+    }
+
+    #[test]
+    fn solve_with_custom_dictionary() {
+        let dictionary = Dictionary::new("test", ["cat", "cats", "tar", "scar"]);
+        let grid = letter_grid(&["cat", "xrs", "xxx"]);
+        assert_eq!(
+            generate_wordlist_from_game(
+                &grid,
+                Topology::Bounded,
+                &GameRules::default(),
+                &dictionary
+            ),
+            ["cat", "cats", "tar"]
+        );
+    }
+
+    #[test]
+    fn solution_paths_spell_words() {
+        let game = Game::from_seed(5, 7);
+        let solution = solve_game(
+            game.grid(),
+            Topology::Bounded,
+            &GameRules::default(),
+            Dictionary::embedded(),
+        );
+        assert!(!solution.is_empty());
+
+        for word in solution.words() {
+            let path = solution.path(word).unwrap();
+            let spelled: String = path
+                .iter()
+                .map(|&(y, x)| game.grid()[y][x].as_str())
+                .collect();
+            assert_eq!(&spelled, word);
+            for pair in path.windows(2) {
+                assert!(pair[0].0.abs_diff(pair[1].0) <= 1 && pair[0].1.abs_diff(pair[1].1) <= 1);
+            }
+            let mut squares = path.clone();
+            squares.sort();
+            squares.dedup();
+            assert_eq!(squares.len(), path.len());
+        }
+    }
+
+    #[test]
+    /// Ensures the embedded dictionary's alphabet is plain lowercase Latin letters
+    fn embedded_alphabet() {
+        let alphabet: String = Dictionary::embedded().alphabet().concat();
+        assert_eq!(alphabet, "abcdefghijklmnopqrstuvwxyz");
     }
 
     #[test]
-    /// Ensures the module's words do not contain non-ascii characters (would panic at runtime)
-    fn no_non_ascii_chars() {
-        assert!(get_words()
-            .iter()
-            .map(|w| w.chars())
-            .flatten()
-            .all(|c| c.is_ascii_lowercase()),);
+    fn accented_and_multi_letter_tiles() {
+        let dictionary =
+            Dictionary::new("fr", ["quête", "thé", "tête", "quota"]).with_tiles(["qu"]);
+        let grid = vec![
+            vec!["qu".to_string(), "ê".to_string(), "t".to_string()],
+            vec!["é".to_string(), "h".to_string(), "e".to_string()],
+            vec!["x".to_string(), "x".to_string(), "x".to_string()],
+        ];
+        let solution = solve_game(&grid, Topology::Bounded, &GameRules::default(), &dictionary);
+        assert_eq!(solution.words().collect::<Vec<_>>(), ["quête", "thé"]);
+        assert_eq!(
+            solution.path("quête").unwrap(),
+            &[(0, 0), (0, 1), (0, 2), (1, 2)]
+        );
+        assert_eq!(
+            generate_wordlist_by_word_search(
+                &grid,
+                Topology::Bounded,
+                &GameRules::default(),
+                &dictionary
+            ),
+            ["quête", "thé"]
+        );
     }
 }
@@ -0,0 +1,143 @@
+use std::{collections::BTreeSet, fs, io, path::Path, sync::OnceLock};
+
+use super::{Grid, Topology, WILDCARD};
+
+static BLOCKLIST: OnceLock<Blocklist> = OnceLock::new();
+
+/// Words that must never be hidden in, found in, or read off a generated board, such as slurs.
+///
+/// Unlike the offensive words a game can choose to leave out, blocked words are left out of every
+/// game. One blocklist applies to the whole process: the list compiled into the crate, unless a
+/// different one is [installed](Blocklist::install) before the first game is generated.
+#[derive(Debug, Default)]
+pub struct Blocklist {
+    words: BTreeSet<String>,
+    /// The length of the longest blocked word, in characters.
+    longest: usize,
+}
+
+impl Blocklist {
+    /// Creates a new `Blocklist` of `words`, which are lowercased.
+    pub fn new<S: AsRef<str>>(words: impl IntoIterator<Item = S>) -> Self {
+        let words: BTreeSet<String> = words
+            .into_iter()
+            .map(|w| w.as_ref().trim().to_lowercase())
+            .filter(|w| !w.is_empty())
+            .collect();
+        Blocklist {
+            longest: words.iter().map(|w| w.chars().count()).max().unwrap_or(0),
+            words,
+        }
+    }
+
+    /// Loads a `Blocklist` from the file at `path`, which lists one word per line.
+    pub fn from_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        Ok(Blocklist::new(text.lines()))
+    }
+
+    /// Makes `blocklist` the one every game is checked against, in place of the list compiled into
+    /// the crate.
+    ///
+    /// Fails, handing `blocklist` back, if a blocklist is already in use.
+    pub fn install(blocklist: Blocklist) -> Result<(), Blocklist> {
+        BLOCKLIST.set(blocklist)
+    }
+
+    /// Returns the blocklist every game is checked against, initializing it to the list compiled
+    /// into the crate on first call if none was installed.
+    pub fn global() -> &'static Blocklist {
+        BLOCKLIST.get_or_init(|| Blocklist::new(include_str!("../../blocklist.txt").lines()))
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.words.contains(word)
+    }
+
+    /// Returns whether a blocked word can be read in a straight line of squares in `grid`, in any
+    /// of the directions squares connect in under `topology`.
+    pub fn is_visible_in(&self, grid: &Grid, topology: Topology) -> bool {
+        self.visible_in(grid, topology).is_some()
+    }
+
+    /// Returns the squares spelling a blocked word in a straight line in `grid`, if there is one,
+    /// as checked by [`Blocklist::is_visible_in`].
+    pub fn visible_in(&self, grid: &Grid, topology: Topology) -> Option<Vec<(usize, usize)>> {
+        let rows = grid.len();
+        let cols = grid.first().map_or(0, Vec::len);
+        for (y, row) in grid.iter().enumerate() {
+            for x in 0..row.len() {
+                for &direction in topology.directions() {
+                    let mut line = String::new();
+                    let mut squares = Vec::new();
+                    let mut square = Some((y, x));
+                    while let Some((sy, sx)) = square {
+                        let tile = &grid[sy][sx];
+                        // blanks could be read as any letter, but aren't one until a word is
+                        // traced through them
+                        if tile.is_empty() || tile == WILDCARD {
+                            break;
+                        }
+                        line.push_str(tile);
+                        squares.push((sy, sx));
+                        if self.contains(&line) {
+                            return Some(squares);
+                        }
+                        if line.chars().count() >= self.longest {
+                            break;
+                        }
+                        // lines wrapping around a torus stop when they come back to the start
+                        square = topology
+                            .step((sy, sx), direction, rows, cols)
+                            .filter(|&next| next != (y, x));
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::words::letter_grid;
+
+    #[test]
+    fn blocked_words() {
+        let blocklist = Blocklist::new(["Drat", "", "rats"]);
+        assert!(blocklist.contains("drat"));
+        assert!(blocklist.contains("rats"));
+        assert!(!blocklist.contains("rat"));
+        assert!(!blocklist.contains(""));
+
+        let path = std::env::temp_dir().join("wordtwist_blocklist_from_file.txt");
+        fs::write(&path, "drat\nrats\n").unwrap();
+        assert!(Blocklist::from_file(&path).unwrap().contains("rats"));
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn visible_words() {
+        let blocklist = Blocklist::new(["drat"]);
+        assert!(blocklist.is_visible_in(&letter_grid(&["drat", "xxxx"]), Topology::Bounded));
+        assert!(blocklist.is_visible_in(&letter_grid(&["tard", "xxxx"]), Topology::Bounded));
+        assert!(blocklist.is_visible_in(
+            &letter_grid(&["dxxx", "xrxx", "xxax", "xxxt"]),
+            Topology::Bounded
+        ));
+        // traced around a corner, rather than in a line
+        assert!(!blocklist.is_visible_in(&letter_grid(&["drxx", "xatx"]), Topology::Bounded));
+        assert_eq!(
+            blocklist.visible_in(&letter_grid(&["xtard", "xxxxx"]), Topology::Bounded),
+            Some(vec![(0, 4), (0, 3), (0, 2), (0, 1)])
+        );
+
+        let wrapped = letter_grid(&["atdr", "xxxx"]);
+        assert!(!blocklist.is_visible_in(&wrapped, Topology::Bounded));
+        assert!(blocklist.is_visible_in(&wrapped, Topology::Torus));
+        // a line doesn't wrap back over its own start
+        assert!(!Blocklist::new(["drxd"])
+            .is_visible_in(&letter_grid(&["drx", "yyy", "zzz"]), Topology::Torus));
+    }
+}
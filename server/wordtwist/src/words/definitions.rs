@@ -0,0 +1,111 @@
+use std::{collections::HashMap, fs, io, path::Path, sync::OnceLock};
+
+static DEFINITIONS: OnceLock<Definitions> = OnceLock::new();
+
+/// Short definitions of words, looked up offline to explain words on the results screen.
+///
+/// No definitions are compiled into the crate. The definitions in use are empty unless a source,
+/// like an extract of WordNet, is [installed](Definitions::install) before they are first looked
+/// up.
+#[derive(Debug, Default)]
+pub struct Definitions {
+    definitions: HashMap<String, Vec<String>>,
+}
+
+impl Definitions {
+    /// Creates new `Definitions` from `lines`, each a word and one of its definitions separated by
+    /// a tab.
+    ///
+    /// Words are lowercased, and may have many definitions over many lines. Lines without a tab,
+    /// or with an empty word or definition, are ignored.
+    pub fn new<S: AsRef<str>>(lines: impl IntoIterator<Item = S>) -> Self {
+        let mut definitions: HashMap<String, Vec<String>> = HashMap::new();
+        for line in lines {
+            let Some((word, definition)) = line.as_ref().split_once('\t') else {
+                continue;
+            };
+            let (word, definition) = (word.trim().to_lowercase(), definition.trim());
+            if word.is_empty() || definition.is_empty() {
+                continue;
+            }
+            definitions
+                .entry(word)
+                .or_default()
+                .push(definition.to_string());
+        }
+        Definitions { definitions }
+    }
+
+    /// Loads `Definitions` from the file at `path`, which lists a word and one of its definitions,
+    /// separated by a tab, on each line.
+    pub fn from_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        Ok(Definitions::new(text.lines()))
+    }
+
+    /// Makes `definitions` the ones looked up by [`Definitions::global`].
+    ///
+    /// Fails, handing `definitions` back, if definitions are already in use.
+    pub fn install(definitions: Definitions) -> Result<(), Definitions> {
+        DEFINITIONS.set(definitions)
+    }
+
+    /// Returns the definitions in use, which are empty if none were installed before the first
+    /// call.
+    pub fn global() -> &'static Definitions {
+        DEFINITIONS.get_or_init(Definitions::default)
+    }
+
+    /// Returns every definition of `word`, in the order they were listed.
+    pub fn get(&self, word: &str) -> &[String] {
+        self.definitions.get(word).map_or(&[], Vec::as_slice)
+    }
+
+    /// Returns the first definition of `word`, if it has any.
+    pub fn first(&self, word: &str) -> Option<&str> {
+        self.get(word).first().map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.definitions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.definitions.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_definitions() {
+        let definitions = Definitions::new([
+            "Cat\ta small domesticated carnivore",
+            "cat\ta spiteful woman gossip",
+            "dog",
+            "\tno word",
+            "owl\t ",
+            "twist\tto turn around",
+        ]);
+        assert_eq!(definitions.len(), 2);
+        assert_eq!(
+            definitions.get("cat"),
+            ["a small domesticated carnivore", "a spiteful woman gossip"]
+        );
+        assert_eq!(definitions.first("twist"), Some("to turn around"));
+        assert!(definitions.get("dog").is_empty());
+        assert_eq!(definitions.first("owl"), None);
+    }
+
+    #[test]
+    fn definitions_from_file() {
+        let path = std::env::temp_dir().join("wordtwist_definitions_from_file.tsv");
+        fs::write(&path, "word\ta unit of language\n").unwrap();
+        let definitions = Definitions::from_file(&path).unwrap();
+        assert_eq!(definitions.first("word"), Some("a unit of language"));
+        assert!(Definitions::from_file(path.with_extension("missing")).is_err());
+        fs::remove_file(path).unwrap();
+    }
+}
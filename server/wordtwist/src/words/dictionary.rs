@@ -0,0 +1,244 @@
+use std::{collections::BTreeSet, fs, io, path::Path, sync::OnceLock};
+
+use crate::trie::Trie;
+
+/// The id of the word list compiled into the crate.
+pub const DEFAULT_DICTIONARY: &str = "default";
+
+static EMBEDDED: OnceLock<Dictionary> = OnceLock::new();
+
+/// A named word list that games can be generated from and validated against.
+///
+/// The dictionary also defines the alphabet of tiles games are built from: every letter that
+/// appears in its words, along with any multi-letter tiles (like "qu") added with
+/// [`Dictionary::with_tiles`].
+#[derive(Debug)]
+pub struct Dictionary {
+    id: String,
+    words: Vec<String>,
+    trie: Trie,
+    alphabet: Vec<String>,
+    tile_frequencies: OnceLock<Vec<(String, usize)>>,
+    /// Words only ever listed capitalized, like names and places.
+    proper_nouns: BTreeSet<String>,
+    offensive: BTreeSet<String>,
+}
+
+impl Dictionary {
+    /// Creates a new `Dictionary` called `id` from `words`, which are lowercased.
+    ///
+    /// Words that are only listed capitalized are remembered as proper nouns.
+    pub fn new<S: AsRef<str>>(id: &str, words: impl IntoIterator<Item = S>) -> Self {
+        let mut capitalized = BTreeSet::new();
+        let mut lowercase = BTreeSet::new();
+        let words: Vec<String> = words
+            .into_iter()
+            .map(|w| {
+                let w = w.as_ref().trim();
+                let lowered = w.to_lowercase();
+                if w.starts_with(char::is_uppercase) {
+                    capitalized.insert(lowered.clone());
+                } else {
+                    lowercase.insert(lowered.clone());
+                }
+                lowered
+            })
+            .filter(|w| !w.is_empty())
+            .collect();
+        let letters: BTreeSet<char> = words.iter().flat_map(|w| w.chars()).collect();
+        Dictionary {
+            id: id.to_string(),
+            trie: Trie::from_words(&words),
+            alphabet: letters.into_iter().map(String::from).collect(),
+            tile_frequencies: OnceLock::new(),
+            proper_nouns: capitalized.difference(&lowercase).cloned().collect(),
+            offensive: BTreeSet::new(),
+            words,
+        }
+    }
+
+    /// Adds multi-letter `tiles` to the dictionary's alphabet, which are lowercased.
+    pub fn with_tiles<S: AsRef<str>>(mut self, tiles: impl IntoIterator<Item = S>) -> Self {
+        for tile in tiles {
+            let tile = tile.as_ref().trim().to_lowercase();
+            if !tile.is_empty() && !self.alphabet.contains(&tile) {
+                self.alphabet.push(tile);
+            }
+        }
+        self
+    }
+
+    /// Marks `words`, which are lowercased, as offensive, so games can choose to leave them out.
+    pub fn with_offensive<S: AsRef<str>>(mut self, words: impl IntoIterator<Item = S>) -> Self {
+        self.offensive.extend(
+            words
+                .into_iter()
+                .map(|w| w.as_ref().trim().to_lowercase())
+                .filter(|w| !w.is_empty()),
+        );
+        self
+    }
+
+    /// Loads a `Dictionary` called `id` from the file at `path`, which lists one word per line.
+    pub fn from_file(id: &str, path: impl AsRef<Path>) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        Ok(Dictionary::new(id, text.lines()))
+    }
+
+    /// Initializes the dictionary compiled into the crate on first call, returns the reference on
+    /// subsequent calls
+    pub fn embedded() -> &'static Dictionary {
+        EMBEDDED.get_or_init(|| {
+            Dictionary::new(DEFAULT_DICTIONARY, include_str!("../../words.txt").lines())
+                .with_offensive(include_str!("../../offensive.txt").lines())
+        })
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.trie.contains(word)
+    }
+
+    /// Returns whether `word` is only listed capitalized, as names and places are.
+    pub fn is_proper_noun(&self, word: &str) -> bool {
+        self.proper_nouns.contains(word)
+    }
+
+    pub fn is_offensive(&self, word: &str) -> bool {
+        self.offensive.contains(word)
+    }
+
+    /// Returns whether `word` is the plural of another word in the dictionary, as "cats", "boxes"
+    /// and "flies" are.
+    pub fn is_plural(&self, word: &str) -> bool {
+        let Some(stem) = word.strip_suffix('s') else {
+            return false;
+        };
+        // "glass" and "boss" aren't plurals
+        if stem.ends_with('s') {
+            return false;
+        }
+        self.contains(stem)
+            || stem.strip_suffix('e').is_some_and(|s| self.contains(s))
+            || stem
+                .strip_suffix("ie")
+                .is_some_and(|s| self.contains(&format!("{s}y")))
+    }
+
+    /// Returns every tile a grid using this dictionary can contain.
+    pub fn alphabet(&self) -> &[String] {
+        &self.alphabet
+    }
+
+    /// Returns how many times each tile of the alphabet occurs across all of the dictionary's words.
+    ///
+    /// Counted on first call, and returned from a cache on subsequent calls.
+    pub fn tile_frequencies(&self) -> &[(String, usize)] {
+        self.tile_frequencies.get_or_init(|| {
+            self.alphabet
+                .iter()
+                .map(|tile| {
+                    let count = self.words.iter().map(|w| w.matches(tile.as_str()).count());
+                    (tile.clone(), count.sum())
+                })
+                .collect()
+        })
+    }
+
+    /// Splits `word` into the tiles of this dictionary's alphabet that spell it, preferring the
+    /// longest tile at each step.
+    ///
+    /// Returns `None` if `word` contains letters outside of the alphabet.
+    pub fn tiles_of(&self, word: &str) -> Option<Vec<String>> {
+        let mut tiles = Vec::new();
+        let mut rest = word;
+        while !rest.is_empty() {
+            let tile = self
+                .alphabet
+                .iter()
+                .filter(|t| rest.starts_with(t.as_str()))
+                .max_by_key(|t| t.len())?;
+            rest = &rest[tile.len()..];
+            tiles.push(tile.clone());
+        }
+        Some(tiles)
+    }
+
+    pub(crate) fn trie(&self) -> &Trie {
+        &self.trie
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dictionary_from_file() {
+        let path = std::env::temp_dir().join("wordtwist_dictionary_from_file.txt");
+        fs::write(&path, "Cat\ndog\n\nbird\n").unwrap();
+
+        let dictionary = Dictionary::from_file("pets", &path).unwrap();
+        assert_eq!(dictionary.id(), "pets");
+        assert_eq!(dictionary.words(), ["cat", "dog", "bird"]);
+        assert!(dictionary.contains("cat"));
+        assert!(!dictionary.contains("ca"));
+        assert!(Dictionary::from_file("missing", path.with_extension("missing")).is_err());
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn alphabet_and_tiles() {
+        let dictionary = Dictionary::new("fr", ["Été", "quête", "lac"]).with_tiles(["Qu"]);
+        assert_eq!(
+            dictionary.alphabet(),
+            ["a", "c", "e", "l", "q", "t", "u", "é", "ê", "qu"]
+        );
+        assert_eq!(dictionary.tiles_of("quête").unwrap(), ["qu", "ê", "t", "e"]);
+        assert_eq!(dictionary.tiles_of("été").unwrap(), ["é", "t", "é"]);
+        assert_eq!(dictionary.tiles_of("zèbre"), None);
+    }
+
+    #[test]
+    fn tile_frequencies() {
+        let dictionary = Dictionary::new("test", ["queue", "quiet"]).with_tiles(["qu"]);
+        let frequencies = dictionary.tile_frequencies();
+        let count = |tile: &str| frequencies.iter().find(|(t, _)| t == tile).unwrap().1;
+
+        assert_eq!(count("u"), 3);
+        assert_eq!(count("e"), 3);
+        assert_eq!(count("qu"), 2);
+        assert_eq!(frequencies.len(), dictionary.alphabet().len());
+    }
+
+    #[test]
+    fn word_classes() {
+        let dictionary = Dictionary::new(
+            "test",
+            [
+                "cat", "cats", "box", "boxes", "fly", "flies", "glass", "Bill", "bill", "Paris",
+            ],
+        )
+        .with_offensive(["Drat"]);
+        assert!(dictionary.is_plural("cats"));
+        assert!(dictionary.is_plural("boxes"));
+        assert!(dictionary.is_plural("flies"));
+        assert!(!dictionary.is_plural("glass"));
+        assert!(!dictionary.is_plural("cat"));
+
+        assert!(dictionary.is_proper_noun("paris"));
+        assert!(!dictionary.is_proper_noun("bill"));
+        assert!(dictionary.contains("paris"));
+
+        assert!(dictionary.is_offensive("drat"));
+        assert!(!dictionary.is_offensive("cat"));
+    }
+}
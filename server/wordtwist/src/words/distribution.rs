@@ -0,0 +1,285 @@
+use std::{error::Error, fmt, str::FromStr};
+
+use rand::{
+    distributions::{Distribution, WeightedIndex},
+    seq::{IteratorRandom, SliceRandom},
+    Rng,
+};
+use serde::{Deserialize, Serialize};
+
+use super::{get_random_tile, Dictionary};
+
+/// The sixteen dice of classic Boggle, one string of faces per die. The "q" face reads "qu".
+const BOGGLE_DICE: [&str; 16] = [
+    "aaeegn", "abbjoo", "achops", "affkps", "aoottw", "cimotu", "deilrx", "delrvy", "distty",
+    "eeghnw", "eeinsu", "ehrtvw", "eiosst", "elrtty", "himnqu", "hlnnrz",
+];
+
+/// How the tiles filling the rest of a grid, around the hidden target word, are chosen.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LetterDistribution {
+    /// Every tile of the dictionary's alphabet, with vowels twice as likely as other tiles.
+    #[default]
+    VowelWeighted,
+    /// Tiles weighted by how often they occur in the dictionary's words.
+    DictionaryFrequency,
+    /// Tiles weighted by an explicit table of tiles and weights.
+    Weights(LetterWeights),
+    /// A face of each of the classic Boggle dice, using every die once before reusing any.
+    BoggleDice,
+}
+
+impl LetterDistribution {
+    /// Draws `count` tiles for a grid using `dictionary`, using `rng`.
+    ///
+    /// Panics if there are no tiles to draw from.
+    pub fn sample_tiles<R: Rng + ?Sized>(
+        &self,
+        dictionary: &Dictionary,
+        count: usize,
+        rng: &mut R,
+    ) -> Vec<String> {
+        match self {
+            LetterDistribution::VowelWeighted => (0..count)
+                .map(|_| get_random_tile(dictionary, rng))
+                .collect(),
+            LetterDistribution::DictionaryFrequency => {
+                sample_weighted(dictionary.tile_frequencies(), count, rng)
+            }
+            LetterDistribution::Weights(weights) => sample_weighted(&weights.0, count, rng),
+            LetterDistribution::BoggleDice => {
+                let mut dice = Vec::with_capacity(count);
+                while dice.len() < count {
+                    let mut set = BOGGLE_DICE;
+                    set.shuffle(rng);
+                    dice.extend(set.into_iter().take(count - dice.len()));
+                }
+                dice.into_iter()
+                    .map(|die| match die.chars().choose(rng).unwrap() {
+                        'q' => "qu".to_string(),
+                        face => face.to_string(),
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// A table of tiles, each weighted by how likely it is to be drawn compared to the others.
+///
+/// Written as comma separated `tile:weight` pairs, like `e:12,t:9,qu:1`, which is also how it's
+/// serialized, so a table can be given in a query string.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct LetterWeights(Vec<(String, usize)>);
+
+/// The reasons a table of [`LetterWeights`] can be rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeightsError {
+    /// A pair isn't a tile and a whole number weight, separated by a colon.
+    Malformed,
+    /// No tile has a weight above zero, so there is nothing to draw.
+    NoPositiveWeight,
+    /// The weights add up to more than can be counted.
+    Overflow,
+}
+
+impl fmt::Display for WeightsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            WeightsError::Malformed => "letter weights must be comma separated tile:weight pairs",
+            WeightsError::NoPositiveWeight => "letter weights must include a positive weight",
+            WeightsError::Overflow => "letter weights are too large",
+        })
+    }
+}
+
+impl Error for WeightsError {}
+
+impl LetterWeights {
+    /// Creates a new table of `weights`, whose tiles are lowercased.
+    ///
+    /// Fails if no tile has a positive weight, or if the weights add up to more than a `usize`.
+    pub fn new<S: AsRef<str>>(
+        weights: impl IntoIterator<Item = (S, usize)>,
+    ) -> Result<Self, WeightsError> {
+        let weights: Vec<(String, usize)> = weights
+            .into_iter()
+            .map(|(tile, weight)| (tile.as_ref().trim().to_lowercase(), weight))
+            .collect();
+        if weights.iter().any(|(tile, _)| tile.is_empty()) {
+            return Err(WeightsError::Malformed);
+        }
+        // tiles are drawn against the total weight, which has to be counted
+        let total = weights
+            .iter()
+            .try_fold(0usize, |total, &(_, weight)| total.checked_add(weight))
+            .ok_or(WeightsError::Overflow)?;
+        if total == 0 {
+            return Err(WeightsError::NoPositiveWeight);
+        }
+        Ok(LetterWeights(weights))
+    }
+
+    /// Returns each tile in the table along with its weight.
+    pub fn weights(&self) -> &[(String, usize)] {
+        &self.0
+    }
+}
+
+impl FromStr for LetterWeights {
+    type Err = WeightsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let weights = s
+            .split(',')
+            .map(|pair| {
+                let (tile, weight) = pair.split_once(':').ok_or(WeightsError::Malformed)?;
+                let weight = weight.trim().parse().map_err(|_| WeightsError::Malformed)?;
+                Ok((tile, weight))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        LetterWeights::new(weights)
+    }
+}
+
+impl fmt::Display for LetterWeights {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (tile, weight)) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{tile}:{weight}")?;
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<String> for LetterWeights {
+    type Error = WeightsError;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<LetterWeights> for String {
+    fn from(weights: LetterWeights) -> Self {
+        weights.to_string()
+    }
+}
+
+/// Draws `count` tiles from `weights`, each tile being chosen in proportion to its weight.
+fn sample_weighted<R: Rng + ?Sized>(
+    weights: &[(String, usize)],
+    count: usize,
+    rng: &mut R,
+) -> Vec<String> {
+    let index = WeightedIndex::new(weights.iter().map(|(_, w)| w))
+        .expect("letter weights are checked to include a positive weight");
+    (0..count)
+        .map(|_| weights[index.sample(rng)].0.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+
+    const SAMPLES: usize = 100_000;
+
+    /// Returns the share of `tiles` that are `tile`.
+    fn share(tiles: &[String], tile: &str) -> f64 {
+        tiles.iter().filter(|t| *t == tile).count() as f64 / tiles.len() as f64
+    }
+
+    #[test]
+    fn vowel_weighted_distribution() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let tiles = LetterDistribution::VowelWeighted.sample_tiles(
+            Dictionary::embedded(),
+            SAMPLES,
+            &mut rng,
+        );
+        assert!((share(&tiles, "e") - 2.0 / 31.0).abs() < 0.005);
+        assert!((share(&tiles, "z") - 1.0 / 31.0).abs() < 0.005);
+    }
+
+    #[test]
+    fn weights_table_distribution() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let weights = LetterDistribution::Weights("a:3,b:1,c:0".parse().unwrap());
+        let tiles = weights.sample_tiles(Dictionary::embedded(), SAMPLES, &mut rng);
+        assert!((share(&tiles, "a") - 0.75).abs() < 0.01);
+        assert!((share(&tiles, "b") - 0.25).abs() < 0.01);
+        assert_eq!(share(&tiles, "c"), 0.0);
+    }
+
+    #[test]
+    fn letter_weights() {
+        let weights: LetterWeights = "E:12, t:9,qu:1".parse().unwrap();
+        assert_eq!(
+            weights.weights(),
+            [
+                ("e".to_string(), 12),
+                ("t".to_string(), 9),
+                ("qu".to_string(), 1)
+            ]
+        );
+        assert_eq!(weights.to_string(), "e:12,t:9,qu:1");
+        assert_eq!(
+            LetterWeights::try_from(String::from(weights.clone())),
+            Ok(weights)
+        );
+
+        assert_eq!("".parse::<LetterWeights>(), Err(WeightsError::Malformed));
+        assert_eq!("e:x".parse::<LetterWeights>(), Err(WeightsError::Malformed));
+        assert_eq!(":3".parse::<LetterWeights>(), Err(WeightsError::Malformed));
+        assert_eq!(
+            "a:0,b:0".parse::<LetterWeights>(),
+            Err(WeightsError::NoPositiveWeight)
+        );
+        assert!(LetterWeights::new(Vec::<(String, usize)>::new()).is_err());
+        assert_eq!(
+            format!("a:{},b:1", usize::MAX).parse::<LetterWeights>(),
+            Err(WeightsError::Overflow)
+        );
+        let heaviest: LetterWeights = format!("a:{},b:0", usize::MAX).parse().unwrap();
+        sample_weighted(heaviest.weights(), 1, &mut ChaCha8Rng::seed_from_u64(0));
+    }
+
+    #[test]
+    fn dictionary_frequency_distribution() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let dictionary = Dictionary::embedded();
+        let tiles =
+            LetterDistribution::DictionaryFrequency.sample_tiles(dictionary, SAMPLES, &mut rng);
+
+        let total: usize = dictionary.tile_frequencies().iter().map(|(_, n)| n).sum();
+        for (tile, count) in dictionary.tile_frequencies() {
+            let expected = *count as f64 / total as f64;
+            assert!((share(&tiles, tile) - expected).abs() < 0.005, "{tile}");
+        }
+        assert!(share(&tiles, "e") > 5.0 * share(&tiles, "j"));
+    }
+
+    #[test]
+    fn boggle_dice_distribution() {
+        let mut rng = ChaCha8Rng::seed_from_u64(0);
+        let dice = LetterDistribution::BoggleDice;
+
+        // a full set of dice always has exactly one die that can show "qu"
+        let tiles = dice.sample_tiles(Dictionary::embedded(), 16, &mut rng);
+        assert!(tiles.iter().all(|t| t != "q"));
+        assert!(tiles.iter().filter(|t| *t == "qu").count() <= 1);
+
+        // each face of each die is equally likely, so "e" shows on 11 of the 96 faces
+        let tiles = dice.sample_tiles(Dictionary::embedded(), SAMPLES, &mut rng);
+        assert!((share(&tiles, "e") - 11.0 / 96.0).abs() < 0.005);
+        assert!((share(&tiles, "qu") - 1.0 / 96.0).abs() < 0.002);
+    }
+}
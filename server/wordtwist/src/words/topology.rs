@@ -0,0 +1,149 @@
+use serde::{Deserialize, Serialize};
+
+/// The eight directions to a neighbouring square, as `(dy, dx)` steps.
+const DIRECTIONS: [(isize, isize); 8] = [
+    (-1, 0),
+    (-1, -1),
+    (-1, 1),
+    (1, 0),
+    (1, -1),
+    (1, 1),
+    (0, -1),
+    (0, 1),
+];
+
+/// The six directions to a neighbouring hexagon, as `(dr, dq)` steps in axial coordinates.
+const HEX_DIRECTIONS: [(isize, isize); 6] = [(-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0)];
+
+/// How the squares of a board connect to each other.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Topology {
+    /// Squares are adjacent to the (up to) eight squares around them, stopping at the edges.
+    #[default]
+    Bounded,
+    /// As [`Topology::Bounded`], but the edges wrap around, so the top row is adjacent to the
+    /// bottom row and the left column to the right column.
+    Torus,
+    /// Squares are hexagons, adjacent to the (up to) six hexagons around them.
+    ///
+    /// The grid holds the board in axial coordinates: `grid[r][q]` is the hexagon in row `r` and
+    /// diagonal column `q`. Each row is drawn shifted half a hexagon right of the row above it, so a
+    /// full grid is a rhombus, and [`Mask::Hexagon`](crate::game::Mask::Hexagon) trims it into a
+    /// hexagon.
+    Hex,
+}
+
+impl Topology {
+    /// Returns the `(dy, dx)` steps from a square to each of its neighbours.
+    pub(crate) fn directions(self) -> &'static [(isize, isize)] {
+        match self {
+            Topology::Hex => &HEX_DIRECTIONS,
+            _ => &DIRECTIONS,
+        }
+    }
+
+    /// Returns the square one `(dy, dx)` step from `(y, x)` on a board of `rows` x `cols` squares,
+    /// if there is one.
+    pub(crate) fn step(
+        self,
+        (y, x): (usize, usize),
+        (dy, dx): (isize, isize),
+        rows: usize,
+        cols: usize,
+    ) -> Option<(usize, usize)> {
+        let step = |i: usize, d: isize, n: usize| match self {
+            Topology::Bounded | Topology::Hex => i.checked_add_signed(d).filter(|&j| j < n),
+            // on boards less than three squares across, stepping either way lands on the same
+            // square, or back where it started
+            Topology::Torus if d != 0 && (n == 1 || (n == 2 && d == 1)) => None,
+            Topology::Torus => Some((i + n).checked_add_signed(d)? % n),
+        };
+        Some((step(y, dy, rows)?, step(x, dx, cols)?))
+    }
+
+    /// Returns the squares adjacent to `(y, x)` on a board of `rows` x `cols` squares.
+    pub fn neighbours(
+        self,
+        square: (usize, usize),
+        rows: usize,
+        cols: usize,
+    ) -> impl Iterator<Item = (usize, usize)> {
+        self.directions()
+            .iter()
+            .filter_map(move |&direction| self.step(square, direction, rows, cols))
+    }
+
+    /// Returns whether `a` and `b` are adjacent squares on a board of `rows` x `cols` squares.
+    pub fn are_adjacent(
+        self,
+        a: (usize, usize),
+        b: (usize, usize),
+        rows: usize,
+        cols: usize,
+    ) -> bool {
+        self.neighbours(a, rows, cols).any(|n| n == b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted_neighbours(
+        topology: Topology,
+        square: (usize, usize),
+        rows: usize,
+        cols: usize,
+    ) -> Vec<(usize, usize)> {
+        let mut neighbours: Vec<_> = topology.neighbours(square, rows, cols).collect();
+        neighbours.sort();
+        neighbours
+    }
+
+    #[test]
+    fn bounded_neighbours() {
+        assert_eq!(
+            sorted_neighbours(Topology::Bounded, (0, 0), 3, 4),
+            [(0, 1), (1, 0), (1, 1)]
+        );
+        assert_eq!(Topology::Bounded.neighbours((1, 1), 3, 3).count(), 8);
+        assert!(!Topology::Bounded.are_adjacent((0, 0), (2, 0), 3, 3));
+    }
+
+    #[test]
+    fn torus_neighbours() {
+        assert_eq!(
+            sorted_neighbours(Topology::Torus, (0, 0), 3, 4),
+            [
+                (0, 1),
+                (0, 3),
+                (1, 0),
+                (1, 1),
+                (1, 3),
+                (2, 0),
+                (2, 1),
+                (2, 3)
+            ]
+        );
+        assert!(Topology::Torus.are_adjacent((0, 0), (2, 3), 3, 4));
+        assert!(!Topology::Torus.are_adjacent((0, 0), (0, 2), 3, 4));
+
+        // narrow boards don't yield a square twice, or the square itself
+        assert_eq!(sorted_neighbours(Topology::Torus, (0, 0), 2, 1), [(1, 0)]);
+    }
+
+    #[test]
+    fn hex_neighbours() {
+        assert_eq!(
+            sorted_neighbours(Topology::Hex, (1, 1), 3, 3),
+            [(0, 1), (0, 2), (1, 0), (1, 2), (2, 0), (2, 1)]
+        );
+        assert_eq!(
+            sorted_neighbours(Topology::Hex, (0, 0), 3, 3),
+            [(0, 1), (1, 0)]
+        );
+        assert!(Topology::Hex.are_adjacent((1, 0), (0, 1), 3, 3));
+        assert!(!Topology::Hex.are_adjacent((0, 0), (1, 1), 3, 3));
+    }
+}